@@ -0,0 +1,9 @@
+CREATE TABLE bucket_overrides (
+    guild_id INT8 NOT NULL,
+    bucket VARCHAR(32) NOT NULL,
+    scope VARCHAR(8) NOT NULL,
+    delay INT4 NOT NULL,
+    limit_window INT4,
+    limit_uses INT4,
+    PRIMARY KEY (guild_id, bucket, scope)
+);
@@ -0,0 +1,9 @@
+CREATE TABLE music_queues (
+    guild_id INT8 NOT NULL,
+    position INT4 NOT NULL,
+    url TEXT NOT NULL,
+    title TEXT,
+    requester_id INT8,
+    offset_ms INT8 NOT NULL DEFAULT 0,
+    PRIMARY KEY (guild_id, position)
+);
@@ -0,0 +1,4 @@
+CREATE TABLE music_settings (
+    guild_id INT8 NOT NULL PRIMARY KEY,
+    vote_skip_percent INT2 NOT NULL DEFAULT 50
+);
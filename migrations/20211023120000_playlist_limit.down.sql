@@ -0,0 +1 @@
+ALTER TABLE music_settings DROP COLUMN playlist_limit;
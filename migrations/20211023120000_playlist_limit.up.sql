@@ -0,0 +1 @@
+ALTER TABLE music_settings ADD COLUMN playlist_limit INT2 NOT NULL DEFAULT 100;
@@ -0,0 +1,16 @@
+CREATE TABLE playlists (
+    playlist_id SERIAL PRIMARY KEY,
+    owner_id INT8 NOT NULL,
+    is_guild BOOL NOT NULL,
+    name VARCHAR(100) NOT NULL,
+    UNIQUE (owner_id, is_guild, name)
+);
+
+CREATE TABLE playlist_tracks (
+    playlist_id INT4 NOT NULL REFERENCES playlists ON DELETE CASCADE,
+    position INT4 NOT NULL,
+    url TEXT NOT NULL,
+    title TEXT,
+    duration_secs INT4,
+    PRIMARY KEY (playlist_id, position)
+);
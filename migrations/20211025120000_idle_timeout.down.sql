@@ -0,0 +1 @@
+ALTER TABLE music_settings DROP COLUMN idle_timeout_mins;
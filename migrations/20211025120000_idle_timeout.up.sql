@@ -0,0 +1 @@
+ALTER TABLE music_settings ADD COLUMN idle_timeout_mins INT2 NOT NULL DEFAULT 5;
@@ -0,0 +1,6 @@
+CREATE TABLE lyrics (
+    query TEXT PRIMARY KEY,
+    title TEXT NOT NULL,
+    lyrics TEXT NOT NULL,
+    cached_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,7 @@
+CREATE TABLE library_tracks (
+    path TEXT PRIMARY KEY,
+    title TEXT,
+    artist TEXT,
+    album TEXT,
+    duration_secs INT4
+);
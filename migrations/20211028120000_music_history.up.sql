@@ -0,0 +1,11 @@
+CREATE TABLE music_history (
+    history_id SERIAL PRIMARY KEY,
+    guild_id INT8 NOT NULL,
+    url TEXT NOT NULL,
+    title TEXT,
+    requester_id INT8,
+    listened_ms INT8 NOT NULL DEFAULT 0,
+    played_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX music_history_guild_idx ON music_history (guild_id, played_at);
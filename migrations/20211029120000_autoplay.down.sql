@@ -0,0 +1,5 @@
+ALTER TABLE music_settings DROP COLUMN autoplay_no_repeat;
+ALTER TABLE music_settings DROP COLUMN autoplay_playlist;
+ALTER TABLE music_settings DROP COLUMN autoplay;
+
+ALTER TABLE music_history DROP COLUMN artist;
@@ -0,0 +1,5 @@
+ALTER TABLE music_history ADD COLUMN artist TEXT;
+
+ALTER TABLE music_settings ADD COLUMN autoplay BOOL NOT NULL DEFAULT false;
+ALTER TABLE music_settings ADD COLUMN autoplay_playlist VARCHAR(100);
+ALTER TABLE music_settings ADD COLUMN autoplay_no_repeat INT2 NOT NULL DEFAULT 20;
@@ -0,0 +1,2 @@
+ALTER TABLE music_settings DROP COLUMN normalize;
+ALTER TABLE music_settings DROP COLUMN volume_percent;
@@ -0,0 +1,2 @@
+ALTER TABLE music_settings ADD COLUMN volume_percent INT2 NOT NULL DEFAULT 100;
+ALTER TABLE music_settings ADD COLUMN normalize BOOL NOT NULL DEFAULT false;
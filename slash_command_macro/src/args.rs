@@ -0,0 +1,295 @@
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{quote, ToTokens};
+use std::convert::TryFrom;
+use syn::{
+    parse_quote, Data, DeriveInput, Error as SynError, Expr, Fields, GenericArgument, Ident, Lit,
+    LitStr, Meta, NestedMeta, PathArguments, Result as SynResult, Type,
+};
+
+pub struct CommandArgs {
+    name: Ident,
+    fields: Vec<ArgField>,
+}
+
+struct ArgField {
+    ident: Ident,
+    ty: Type,
+    name: LitStr,
+    description: LitStr,
+    kind: FieldKind,
+    min: Option<i64>,
+    max: Option<i64>,
+    channel_types: Vec<Ident>,
+}
+
+enum FieldKind {
+    Required,
+    Optional,
+    Default(Box<Expr>),
+}
+
+impl TryFrom<DeriveInput> for CommandArgs {
+    type Error = SynError;
+
+    fn try_from(input: DeriveInput) -> SynResult<Self> {
+        let named = match input.data {
+            Data::Struct(data) => match data.fields {
+                Fields::Named(fields) => fields.named,
+                Fields::Unit => Default::default(),
+                Fields::Unnamed(fields) => {
+                    let message = "`CommandArgs` can only be derived for structs with named fields";
+
+                    return Err(SynError::new_spanned(fields, message));
+                }
+            },
+            _ => {
+                let message = "`CommandArgs` can only be derived for structs";
+
+                return Err(SynError::new(input.ident.span(), message));
+            }
+        };
+
+        let mut fields = Vec::with_capacity(named.len());
+
+        for field in named {
+            let ident = field.ident.expect("named field");
+
+            let mut name = None;
+            let mut description = None;
+            let mut default = None;
+            let mut min = None;
+            let mut max = None;
+            let mut channel_types = Vec::new();
+
+            for attr in field.attrs {
+                if !attr.path.is_ident("option") {
+                    continue;
+                }
+
+                let list = match attr.parse_meta()? {
+                    Meta::List(list) => list,
+                    other => {
+                        let message = r#"attribute must be of the form `#[option(... = "...")]`"#;
+
+                        return Err(SynError::new_spanned(other, message));
+                    }
+                };
+
+                for nested in list.nested {
+                    let m = match nested {
+                        NestedMeta::Meta(Meta::NameValue(m)) => m,
+                        other => {
+                            let message = r#"expected `... = "..."`"#;
+
+                            return Err(SynError::new_spanned(other, message));
+                        }
+                    };
+
+                    if m.path == parse_quote!(name) {
+                        name = Some(lit_str(m.lit, "name")?);
+                    } else if m.path == parse_quote!(description) {
+                        description = Some(lit_str(m.lit, "description")?);
+                    } else if m.path == parse_quote!(default) {
+                        default = Some(lit_str(m.lit, "default")?.parse::<Expr>()?);
+                    } else if m.path == parse_quote!(min) {
+                        min = Some(lit_int(m.lit, "min")?);
+                    } else if m.path == parse_quote!(max) {
+                        max = Some(lit_int(m.lit, "max")?);
+                    } else if m.path == parse_quote!(channel_types) {
+                        let lit = lit_str(m.lit, "channel_types")?;
+
+                        for kind in lit.value().split('|') {
+                            channel_types.push(Ident::new(kind.trim(), lit.span()));
+                        }
+                    } else {
+                        let message = "invalid option key, expected either `channel_types`, `default`, `description`, `max`, `min`, or `name`";
+
+                        return Err(SynError::new_spanned(m.path, message));
+                    }
+                }
+            }
+
+            let description = description.ok_or_else(|| {
+                let message = r#"attribute `#[option(description = "...")]` must be specified"#;
+
+                SynError::new(ident.span(), message)
+            })?;
+
+            let name = name.unwrap_or_else(|| LitStr::new(&ident.to_string(), ident.span()));
+
+            let (ty, kind) = match (option_inner(&field.ty).cloned(), default) {
+                (Some(_), Some(default)) => {
+                    let message = "optional fields can't have a default value";
+
+                    return Err(SynError::new_spanned(default, message));
+                }
+                (Some(inner), None) => (inner, FieldKind::Optional),
+                (None, Some(default)) => (field.ty, FieldKind::Default(Box::new(default))),
+                (None, None) => (field.ty, FieldKind::Required),
+            };
+
+            fields.push(ArgField {
+                ident,
+                ty,
+                name,
+                description,
+                kind,
+                min,
+                max,
+                channel_types,
+            });
+        }
+
+        // Discord requires required options to come before optional ones
+        fields.sort_by_key(|field| !matches!(field.kind, FieldKind::Required));
+
+        Ok(Self {
+            name: input.ident,
+            fields,
+        })
+    }
+}
+
+fn lit_str(lit: Lit, key: &str) -> SynResult<LitStr> {
+    match lit {
+        Lit::Str(lit) => Ok(lit),
+        other => {
+            let message = format!(r#"expected `{} = "..."`"#, key);
+
+            Err(SynError::new(other.span(), message))
+        }
+    }
+}
+
+fn lit_int(lit: Lit, key: &str) -> SynResult<i64> {
+    match &lit {
+        Lit::Int(int) => int.base10_parse(),
+        Lit::Str(s) => s.value().parse().map_err(|_| {
+            let message = format!("expected an integer for `{}`", key);
+
+            SynError::new(s.span(), message)
+        }),
+        other => {
+            let message = format!("expected an integer for `{}`", key);
+
+            Err(SynError::new(other.span(), message))
+        }
+    }
+}
+
+/// Returns `T` if the type is of the form `Option<T>`
+fn option_inner(ty: &Type) -> Option<&Type> {
+    let segment = match ty {
+        Type::Path(path) if path.qself.is_none() => path.path.segments.last()?,
+        _ => return None,
+    };
+
+    if segment.ident != "Option" {
+        return None;
+    }
+
+    match &segment.arguments {
+        PathArguments::AngleBracketed(args) if args.args.len() == 1 => match args.args.first()? {
+            GenericArgument::Type(ty) => Some(ty),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+impl ArgField {
+    fn info(&self) -> TokenStream2 {
+        let Self {
+            name,
+            description,
+            kind,
+            min,
+            max,
+            channel_types,
+            ..
+        } = self;
+
+        let required = matches!(kind, FieldKind::Required);
+        let min = optional_tokens(min.as_ref());
+        let max = optional_tokens(max.as_ref());
+
+        quote! {
+            crate::commands::OptionInfo {
+                name: #name,
+                description: #description,
+                required: #required,
+                min: #min,
+                max: #max,
+                channel_types: &[#(::twilight_model::channel::ChannelType::#channel_types),*],
+            }
+        }
+    }
+}
+
+fn optional_tokens(value: Option<&i64>) -> TokenStream2 {
+    match value {
+        Some(value) => quote! { Some(#value) },
+        None => quote! { None },
+    }
+}
+
+impl ToTokens for CommandArgs {
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        let Self { name, fields } = self;
+
+        let idents: Vec<_> = fields.iter().map(|field| &field.ident).collect();
+        let types: Vec<_> = fields.iter().map(|field| &field.ty).collect();
+        let names: Vec<_> = fields.iter().map(|field| &field.name).collect();
+        let infos: Vec<_> = fields.iter().map(ArgField::info).collect();
+
+        let finish = fields.iter().map(|field| {
+            let ident = &field.ident;
+            let name = &field.name;
+
+            match &field.kind {
+                FieldKind::Required => quote! {
+                    #ident.ok_or(crate::error::ArgsError::Missing { name: #name })?
+                },
+                FieldKind::Optional => quote! { #ident },
+                FieldKind::Default(default) => quote! { #ident.unwrap_or_else(|| #default) },
+            }
+        });
+
+        let options = quote! {
+            impl #name {
+                pub fn options() -> Vec<::twilight_model::application::command::CommandOption> {
+                    vec![
+                        #(<#types as crate::commands::OptionValue>::create_option(&#infos),)*
+                    ]
+                }
+
+                pub async fn parse_options(
+                    _: ::std::sync::Arc<crate::Context>,
+                    data: ::twilight_model::application::interaction::application_command::CommandData,
+                ) -> crate::BotResult<Self> {
+                    #(let mut #idents = None;)*
+                    let resolved = data.resolved.as_ref();
+
+                    for option in data.options.iter() {
+                        match crate::commands::option_name(option) {
+                            #(
+                                #names => {
+                                    let info = #infos;
+                                    let value = <#types as crate::commands::OptionValue>::parse_option(&info, option, resolved)?;
+                                    #idents = Some(value);
+                                }
+                            )*
+                            _ => {}
+                        }
+                    }
+
+                    Ok(Self {
+                        #(#idents: #finish,)*
+                    })
+                }
+            }
+        };
+
+        tokens.extend(options);
+    }
+}
@@ -0,0 +1,168 @@
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{quote, ToTokens};
+use std::convert::TryFrom;
+use syn::{
+    parse_quote, Data, DeriveInput, Error as SynError, Fields, Ident, Lit, LitStr, Meta,
+    NestedMeta, Result as SynResult,
+};
+
+pub struct CommandChoices {
+    name: Ident,
+    variants: Vec<Choice>,
+}
+
+struct Choice {
+    ident: Ident,
+    name: LitStr,
+    value: LitStr,
+}
+
+impl TryFrom<DeriveInput> for CommandChoices {
+    type Error = SynError;
+
+    fn try_from(input: DeriveInput) -> SynResult<Self> {
+        let data = match input.data {
+            Data::Enum(data) => data,
+            _ => {
+                let message = "`CommandChoices` can only be derived for enums";
+
+                return Err(SynError::new(input.ident.span(), message));
+            }
+        };
+
+        let mut variants = Vec::with_capacity(data.variants.len());
+
+        for variant in data.variants {
+            if !matches!(variant.fields, Fields::Unit) {
+                let message = "`CommandChoices` variants can't have fields";
+
+                return Err(SynError::new_spanned(variant.fields, message));
+            }
+
+            let mut name = None;
+            let mut value = None;
+
+            for attr in variant.attrs {
+                if !attr.path.is_ident("choice") {
+                    continue;
+                }
+
+                let list = match attr.parse_meta()? {
+                    Meta::List(list) => list,
+                    other => {
+                        let message = r#"attribute must be of the form `#[choice(... = "...")]`"#;
+
+                        return Err(SynError::new_spanned(other, message));
+                    }
+                };
+
+                for nested in list.nested {
+                    match nested {
+                        NestedMeta::Meta(Meta::NameValue(m)) => match m.lit {
+                            Lit::Str(lit) if m.path == parse_quote!(name) => name = Some(lit),
+                            Lit::Str(lit) if m.path == parse_quote!(value) => value = Some(lit),
+                            _ => {
+                                let message =
+                                    r#"expected either `name = "..."` or `value = "..."`"#;
+
+                                return Err(SynError::new_spanned(m.path, message));
+                            }
+                        },
+                        other => {
+                            let message = r#"expected `... = "..."`"#;
+
+                            return Err(SynError::new_spanned(other, message));
+                        }
+                    }
+                }
+            }
+
+            let ident = variant.ident;
+            let name = name.unwrap_or_else(|| LitStr::new(&ident.to_string(), ident.span()));
+
+            let value = value.unwrap_or_else(|| {
+                let value = ident.to_string().to_ascii_lowercase();
+
+                LitStr::new(&value, ident.span())
+            });
+
+            variants.push(Choice { ident, name, value });
+        }
+
+        Ok(Self {
+            name: input.ident,
+            variants,
+        })
+    }
+}
+
+impl ToTokens for CommandChoices {
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        let Self { name, variants } = self;
+
+        let idents: Vec<_> = variants.iter().map(|choice| &choice.ident).collect();
+        let names: Vec<_> = variants.iter().map(|choice| &choice.name).collect();
+        let values: Vec<_> = variants.iter().map(|choice| &choice.value).collect();
+
+        let choices = quote! {
+            impl #name {
+                pub fn name(&self) -> &'static str {
+                    match self {
+                        #(Self::#idents => #names,)*
+                    }
+                }
+
+                pub fn value(&self) -> &'static str {
+                    match self {
+                        #(Self::#idents => #values,)*
+                    }
+                }
+            }
+
+            impl crate::commands::OptionValue for #name {
+                fn create_option(
+                    info: &crate::commands::OptionInfo,
+                ) -> ::twilight_model::application::command::CommandOption {
+                    let choices = vec![
+                        #(
+                            ::twilight_model::application::command::CommandOptionChoice::String {
+                                name: #names.to_owned(),
+                                value: #values.to_owned(),
+                            },
+                        )*
+                    ];
+
+                    let data = ::twilight_model::application::command::ChoiceCommandOptionData {
+                        choices,
+                        description: info.description.to_owned(),
+                        name: info.name.to_owned(),
+                        required: info.required,
+                    };
+
+                    ::twilight_model::application::command::CommandOption::String(data)
+                }
+
+                fn parse_option(
+                    info: &crate::commands::OptionInfo,
+                    option: &::twilight_model::application::interaction::application_command::CommandDataOption,
+                    _: Option<&::twilight_model::application::interaction::application_command::CommandInteractionDataResolved>,
+                ) -> Result<Self, crate::error::ArgsError> {
+                    use ::twilight_model::application::interaction::application_command::CommandDataOption;
+
+                    match option {
+                        CommandDataOption::String { value, .. } => match value.as_str() {
+                            #(#values => Ok(Self::#idents),)*
+                            _ => Err(crate::error::ArgsError::InvalidChoice {
+                                name: info.name,
+                                value: value.to_owned(),
+                            }),
+                        },
+                        _ => Err(crate::error::ArgsError::InvalidType { name: info.name }),
+                    }
+                }
+            }
+        };
+
+        tokens.extend(choices);
+    }
+}
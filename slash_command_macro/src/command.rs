@@ -12,6 +12,17 @@ pub struct Command {
     run_name: Ident,
     args_name: Option<Ident>,
     options_name: Option<Ident>,
+    bucket: Option<Bucket>,
+    guild_only: bool,
+    owner_only: bool,
+    in_voice: bool,
+    permissions: Vec<Ident>,
+    subcommands: Vec<Ident>,
+}
+
+enum Bucket {
+    Named(Ident),
+    Cooldown(u64),
 }
 
 impl TryFrom<Base> for Command {
@@ -23,14 +34,28 @@ impl TryFrom<Base> for Command {
         let mut run_name = None;
         let mut args_name = None;
         let mut options_name = None;
+        let mut bucket = None;
+        let mut guild_only = false;
+        let mut owner_only = false;
+        let mut in_voice = false;
+        let mut permissions = Vec::new();
+        let mut subcommands = Vec::new();
 
         for attr in base.attributes {
             match attr.parse_meta()? {
                 Meta::Path(m) => {
-                    let message = r#"attribute must be of the form `#[... = "..."]`"#;
-                    let span = m.get_ident().map_or_else(Span::call_site, Ident::span);
+                    if m == parse_quote!(guild_only) {
+                        guild_only = true;
+                    } else if m == parse_quote!(owner_only) {
+                        owner_only = true;
+                    } else if m == parse_quote!(in_voice) {
+                        in_voice = true;
+                    } else {
+                        let message = "invalid attribute, expected either `guild_only`, `in_voice`, or `owner_only`";
+                        let span = m.get_ident().map_or_else(Span::call_site, Ident::span);
 
-                    return Err(SynError::new(span, message));
+                        return Err(SynError::new(span, message));
+                    }
                 }
                 Meta::List(m) => {
                     let message = r#"attribute must be of the form `#[... = "..."]`"#;
@@ -63,11 +88,92 @@ impl TryFrom<Base> for Command {
                             other => {
                                 let message = r#"`options` attribute must be of the form `#[options = "..."]`"#;
 
+                                return Err(SynError::new(other.span(), message));
+                            }
+                        }
+                    } else if m.path == parse_quote!(bucket) {
+                        if bucket.is_some() {
+                            let message = "only one of `bucket` and `cooldown` can be specified";
+
+                            return Err(SynError::new(m.lit.span(), message));
+                        }
+
+                        match m.lit {
+                            Lit::Str(lit) => {
+                                bucket = Some(Bucket::Named(Ident::new(&lit.value(), lit.span())))
+                            }
+                            other => {
+                                let message =
+                                    r#"`bucket` attribute must be of the form `#[bucket = "..."]`"#;
+
+                                return Err(SynError::new(other.span(), message));
+                            }
+                        }
+                    } else if m.path == parse_quote!(cooldown) {
+                        if bucket.is_some() {
+                            let message = "only one of `bucket` and `cooldown` can be specified";
+
+                            return Err(SynError::new(m.lit.span(), message));
+                        }
+
+                        let secs = match &m.lit {
+                            Lit::Str(lit) => lit.value().parse().ok(),
+                            Lit::Int(lit) => lit.base10_parse().ok(),
+                            _ => None,
+                        };
+
+                        match secs {
+                            Some(secs) => bucket = Some(Bucket::Cooldown(secs)),
+                            None => {
+                                let message = r#"`cooldown` attribute must be of the form `#[cooldown = "<seconds>"]`"#;
+
+                                return Err(SynError::new(m.lit.span(), message));
+                            }
+                        }
+                    } else if m.path == parse_quote!(required_permissions) {
+                        match m.lit {
+                            Lit::Str(lit) => {
+                                for permission in lit.value().split('|') {
+                                    let permission = permission.trim();
+
+                                    if permission.is_empty() {
+                                        let message = "expected permissions separated by `|`";
+
+                                        return Err(SynError::new(lit.span(), message));
+                                    }
+
+                                    permissions.push(Ident::new(permission, lit.span()));
+                                }
+                            }
+                            other => {
+                                let message = r#"`required_permissions` attribute must be of the form `#[required_permissions = "..."]`"#;
+
+                                return Err(SynError::new(other.span(), message));
+                            }
+                        }
+                    } else if m.path == parse_quote!(subcommands) {
+                        match m.lit {
+                            Lit::Str(lit) => {
+                                for subcommand in lit.value().split(',') {
+                                    let subcommand = subcommand.trim();
+
+                                    if subcommand.is_empty() {
+                                        let message = "expected subcommands separated by `,`";
+
+                                        return Err(SynError::new(lit.span(), message));
+                                    }
+
+                                    subcommands.push(Ident::new(subcommand, lit.span()));
+                                }
+                            }
+                            other => {
+                                let message = r#"`subcommands` attribute must be of the form `#[subcommands = "..."]`"#;
+
                                 return Err(SynError::new(other.span(), message));
                             }
                         }
                     } else {
-                        let message = "invalid attribute key, expected either `args`, `description`, `name`, `options`, or `run`";
+                        let message = "invalid attribute key, expected either `args`, `bucket`, `cooldown`, `description`, `name`, `options`, `required_permissions`, `run`, or `subcommands`";
                         let span = m.path.get_ident().map_or_else(Span::call_site, Ident::span);
 
                         return Err(SynError::new(span, message));
@@ -82,6 +188,14 @@ impl TryFrom<Base> for Command {
             SynError::new(Span::call_site(), message)
         })?;
 
+        if !subcommands.is_empty()
+            && (args_name.is_some() || options_name.is_some() || run_name.is_some())
+        {
+            let message = "commands with `subcommands` can't specify `args`, `options`, or `run`";
+
+            return Err(SynError::new(base.name.span(), message));
+        }
+
         let cmd_name = match cmd_name {
             Some(name) => name,
             None => {
@@ -108,6 +222,13 @@ impl TryFrom<Base> for Command {
             run_name,
             args_name,
             options_name,
+            bucket,
+            // Being in a voice channel requires being in a guild
+            guild_only: guild_only || in_voice,
+            owner_only,
+            in_voice,
+            permissions,
+            subcommands,
         };
 
         Ok(command)
@@ -123,10 +244,39 @@ impl ToTokens for Command {
             run_name,
             args_name,
             options_name,
+            bucket,
+            guild_only,
+            owner_only,
+            in_voice,
+            permissions,
+            subcommands,
         } = self;
 
         let fut_name = format_ident!("{}Future", name);
 
+        let bucket = match bucket {
+            Some(Bucket::Named(bucket)) => quote! {
+                Some(crate::buckets::BucketName::#bucket)
+            },
+            Some(Bucket::Cooldown(secs)) => quote! {
+                Some(crate::buckets::BucketName::Cooldown {
+                    command: #cmd_name,
+                    secs: #secs,
+                })
+            },
+            None => quote! { None },
+        };
+
+        let permissions = if permissions.is_empty() {
+            quote! { None }
+        } else {
+            quote! {
+                Some(::twilight_model::guild::Permissions::from_bits_truncate(
+                    #(::twilight_model::guild::Permissions::#permissions.bits())|*
+                ))
+            }
+        };
+
         let base_stream = quote! {
             pub struct #name;
 
@@ -144,86 +294,194 @@ impl ToTokens for Command {
 
             impl #name {
                 pub const NAME: &'static str = #cmd_name;
+                pub const BUCKET: Option<crate::buckets::BucketName> = #bucket;
+                pub const CHECKS: crate::commands::CommandChecks = crate::commands::CommandChecks {
+                    guild_only: #guild_only,
+                    owner_only: #owner_only,
+                    in_voice: #in_voice,
+                    permissions: #permissions,
+                };
             }
         };
 
         tokens.extend(base_stream);
 
-        let define_stream = if let Some(options_name) = options_name {
+        let options = match (options_name, args_name) {
+            (Some(options_name), _) => quote! { #options_name() },
+            (None, Some(args_name)) => quote! { #args_name::options() },
+            (None, None) if !subcommands.is_empty() => quote! {
+                vec![#(#subcommands::define_option(),)*]
+            },
+            (None, None) => quote! { Vec::new() },
+        };
+
+        // Nested commands with subcommands of their own become subcommand groups
+        let option_kind = if subcommands.is_empty() {
+            quote! { SubCommand }
+        } else {
+            quote! { SubCommandGroup }
+        };
+
+        let define_stream = quote! {
+            impl #name {
+                pub fn define() -> ::twilight_model::application::command::Command {
+                    ::twilight_model::application::command::Command {
+                        application_id: None,
+                        guild_id: None,
+                        name: #cmd_name.to_owned(),
+                        // Checks only run when the command is used, see `check`
+                        default_permission: Some(true),
+                        description: #cmd_description.to_owned(),
+                        id: None,
+                        kind: ::twilight_model::application::command::CommandType::ChatInput,
+                        options: #options,
+                        // version: ::twilight_model::id::CommandVersionId::new(1).unwrap()
+                    }
+                }
+
+                pub fn define_option() -> ::twilight_model::application::command::CommandOption {
+                    let data = ::twilight_model::application::command::OptionsCommandOptionData {
+                        description: #cmd_description.to_owned(),
+                        name: #cmd_name.to_owned(),
+                        options: #options,
+                        required: false,
+                    };
+
+                    ::twilight_model::application::command::CommandOption::#option_kind(data)
+                }
+            }
+        };
+
+        tokens.extend(define_stream);
+
+        let bucket_stream = if subcommands.is_empty() {
             quote! {
                 impl #name {
-                    pub fn define() -> ::twilight_model::application::command::Command {
-                        ::twilight_model::application::command::Command {
-                            application_id: None,
-                            guild_id: None,
-                            name: #cmd_name.to_owned(),
-                            default_permission: None,
-                            description: #cmd_description.to_owned(),
-                            id: None,
-                            kind: ::twilight_model::application::command::CommandType::ChatInput,
-                            options: #options_name(),
-                            // version: ::twilight_model::id::CommandVersionId::new(1).unwrap()
-                        }
+                    pub fn bucket(
+                        _: &[::twilight_model::application::interaction::application_command::CommandDataOption],
+                    ) -> Option<crate::buckets::BucketName> {
+                        Self::BUCKET
                     }
                 }
             }
         } else {
             quote! {
                 impl #name {
-                    pub fn define() -> ::twilight_model::application::command::Command {
-                        ::twilight_model::application::command::Command {
-                            application_id: None,
-                            guild_id: None,
-                            name: #cmd_name.to_owned(),
-                            default_permission: None,
-                            description: #cmd_description.to_owned(),
-                            id: None,
-                            kind: ::twilight_model::application::command::CommandType::ChatInput,
-                            options: Vec::new(),
-                            // version: ::twilight_model::id::CommandVersionId::new(1).unwrap()
-                        }
+                    pub fn bucket(
+                        options: &[::twilight_model::application::interaction::application_command::CommandDataOption],
+                    ) -> Option<crate::buckets::BucketName> {
+                        use ::twilight_model::application::interaction::application_command::CommandDataOption;
+
+                        let bucket = match options.first() {
+                            Some(CommandDataOption::SubCommand { name, options }) => match name.as_str() {
+                                #(#subcommands::NAME => #subcommands::bucket(options),)*
+                                _ => None,
+                            },
+                            _ => None,
+                        };
+
+                        bucket.or(Self::BUCKET)
                     }
                 }
             }
         };
 
-        tokens.extend(define_stream);
+        tokens.extend(bucket_stream);
 
-        let run_stream = if let Some(args_name) = args_name {
+        let check_stream = if subcommands.is_empty() {
             quote! {
                 impl #name {
-                    pub fn run(ctx: ::std::sync::Arc<crate::Context>, mut command: ::twilight_model::application::interaction::ApplicationCommand) -> #fut_name<'static> {
-                        use futures::TryFutureExt;
-
-                        let data = ::std::mem::take(&mut command.data);
-
-                        let fut = #args_name::parse_options(Arc::clone(&ctx), data)
-                            .and_then(|args| #run_name(ctx, command, args))
-                            .map_err(Box::new)
-                            .map_err(|src| crate::Error::Command {
-                                name: #cmd_name,
-                                src,
-                            });
-
-                        #fut_name { fut: Box::pin(fut) }
+                    pub fn check(
+                        ctx: &crate::Context,
+                        command: &::twilight_model::application::interaction::ApplicationCommand,
+                        _: &[::twilight_model::application::interaction::application_command::CommandDataOption],
+                    ) -> crate::BotResult<Option<String>> {
+                        Self::CHECKS.check(ctx, command)
                     }
                 }
             }
         } else {
             quote! {
                 impl #name {
-                    pub fn run(ctx: ::std::sync::Arc<crate::Context>, command: ::twilight_model::application::interaction::ApplicationCommand) -> #fut_name<'static> {
-                        use futures::TryFutureExt;
+                    pub fn check(
+                        ctx: &crate::Context,
+                        command: &::twilight_model::application::interaction::ApplicationCommand,
+                        options: &[::twilight_model::application::interaction::application_command::CommandDataOption],
+                    ) -> crate::BotResult<Option<String>> {
+                        use ::twilight_model::application::interaction::application_command::CommandDataOption;
+
+                        if let Some(content) = Self::CHECKS.check(ctx, command)? {
+                            return Ok(Some(content));
+                        }
 
-                        let fut = #run_name(ctx, command)
-                            .map_err(Box::new)
-                            .map_err(|src| crate::Error::Command {
-                                name: #cmd_name,
-                                src,
-                            });
+                        match options.first() {
+                            Some(CommandDataOption::SubCommand { name, options }) => match name.as_str() {
+                                #(#subcommands::NAME => #subcommands::check(ctx, command, options),)*
+                                _ => Ok(None),
+                            },
+                            _ => Ok(None),
+                        }
+                    }
+                }
+            }
+        };
+
+        tokens.extend(check_stream);
+
+        let execute = if !subcommands.is_empty() {
+            quote! {
+                use ::twilight_model::application::interaction::application_command::CommandDataOption;
 
-                        #fut_name { fut: Box::pin(fut) }
+                // Replace the options with the ones of the chosen subcommand before passing it on
+                let name = match command.data.options.pop() {
+                    Some(CommandDataOption::SubCommand { name, options }) => {
+                        command.data.options = options;
+
+                        name
+                    }
+                    _ => {
+                        return Err(crate::Error::UnknownInteraction {
+                            command: Box::new(command),
+                        })
                     }
+                };
+
+                match name.as_str() {
+                    #(#subcommands::NAME => #subcommands::run(ctx, command).await,)*
+                    _ => Err(crate::Error::UnknownInteraction {
+                        command: Box::new(command),
+                    }),
+                }
+            }
+        } else if let Some(args_name) = args_name {
+            quote! {
+                let data = ::std::mem::take(&mut command.data);
+                let args = #args_name::parse_options(::std::sync::Arc::clone(&ctx), data).await?;
+
+                #run_name(ctx, command, args).await
+            }
+        } else {
+            quote! { #run_name(ctx, command).await }
+        };
+
+        let run_stream = quote! {
+            impl #name {
+                #[allow(unused_mut)]
+                pub fn run(ctx: ::std::sync::Arc<crate::Context>, mut command: ::twilight_model::application::interaction::ApplicationCommand) -> #fut_name<'static> {
+                    use futures::TryFutureExt;
+
+                    let fut = async move {
+                        #execute
+                    };
+
+                    let fut = fut
+                        .map_err(Box::new)
+                        .map_err(|src| crate::Error::Command {
+                            name: #cmd_name,
+                            src,
+                        });
+
+                    #fut_name { fut: Box::pin(fut) }
                 }
             }
         };
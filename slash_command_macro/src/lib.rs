@@ -1,5 +1,9 @@
+mod args;
+mod choices;
 mod command;
 
+use args::CommandArgs;
+use choices::CommandChoices;
 use command::Command;
 
 use proc_macro::TokenStream;
@@ -8,25 +12,48 @@ use quote::quote;
 use std::convert::TryFrom;
 use syn::{
     parse::{Parse, ParseStream},
-    parse_macro_input, Attribute, Error as SynError, Ident, Result as SynResult, Token, Visibility,
+    parse_macro_input, Attribute, DeriveInput, Error as SynError, Ident, Result as SynResult,
+    Token, Visibility,
 };
 
 /// Writes the boilerplate code required for slash commands.
 ///
 /// The following additional attributes can be specified:
 /// - `#[args = "..."]` to specify the type for arguments.
-/// The type must implement the function `async fn parse_options(Arc<Context>, CommandData) -> BotResult<Self>`.
+///   The type must implement the function `async fn parse_options(Arc<Context>, CommandData) -> BotResult<Self>`.
+/// - `#[bucket = "..."]` to put the command into the given `BucketName` variant for ratelimiting.
+/// - `#[cooldown = "..."]` to give the command its own per-user cooldown in seconds.
+///   Mutually exclusive with `bucket`.
 /// - `#[description = "..."]` must be specified to define the command's description.
+/// - `#[guild_only]` to reject the command when used in DMs.
+/// - `#[in_voice]` to reject the command when the author is not in a voice channel. Implies `guild_only`.
 /// - `#[name = "..."]` for the command name. Defaults to the lowercase struct name.
 /// - `#[options = "..."]` for the function name that returns the command options as `Vec<CommandOption>`.
-/// If none is specified the defined command won't have options.
+///   If none is specified, the options are taken from `args` which is expected to `#[derive(CommandArgs)]`.
+///   Otherwise the defined command won't have options.
+/// - `#[owner_only]` to only allow the bot owner to use the command.
+/// - `#[required_permissions = "..."]` for permissions the author must have, separated by `|`
+///   e.g. `#[required_permissions = "MANAGE_GUILD | MOVE_MEMBERS"]`.
 /// - `#[run = "..."` for the function name that runs the command. Defaults to the lowercase struct name.
+/// - `#[subcommands = "..."]` for the command structs that are nested within this command, separated by `,`.
+///   A command with subcommands only dispatches to them so it can't specify `args`, `options`, or `run`.
+///   Nesting a command with subcommands into another one turns it into a subcommand group.
+///
+/// The checks `guild_only`, `in_voice`, `owner_only` and `required_permissions` only run when the command is used.
+/// They don't change `C::define()`, Discord offers every command to everyone and rejected users get an error message.
 ///
 /// For a given command struct `C` this macro enables:
 /// - `C::NAME -> &'static str` for the command name
+/// - `C::BUCKET -> Option<BucketName>` for the ratelimit bucket of the command
+/// - `C::CHECKS -> CommandChecks` for the preconditions that are validated before running the command
 /// - `C::define() -> Command` as function that returns the twilight command
+/// - `C::define_option() -> CommandOption` as function that returns the command as subcommand (group)
+/// - `C::bucket(&[CommandDataOption]) -> Option<BucketName>` for the bucket of the invoked (sub)command
+/// - `C::check(&Context, &ApplicationCommand, &[CommandDataOption]) -> BotResult<Option<String>>`
+///   for the reason why the invoked (sub)command can't be used, checking the parent commands first.
+///   Must be called before `run` which doesn't validate the checks itself.
 /// - `async C::run(Arc<Context>, ApplicationCommand) -> BotResult<()>` as function that runs the command.
-/// If the `args` attribute was specified, there will be a third parameter of the type specified in the attribute.
+///   If the `args` attribute was specified, there will be a third parameter of the type specified in the attribute.
 ///
 /// ## Example: Ping
 ///
@@ -42,6 +69,22 @@ use syn::{
 /// }
 /// ```
 ///
+/// ## Example: Subcommands
+///
+/// ```ignore
+/// #[command]
+/// #[description = "Manage the queue"]
+/// #[subcommands = "QueueShow, QueueClear"]
+/// pub struct Queue;
+///
+/// #[command]
+/// #[description = "Play music"]
+/// #[subcommands = "Play, Queue"]
+/// pub struct Music;
+/// ```
+///
+/// This defines `/music play` and the subcommand group `/music queue` with `show` and `clear`.
+///
 /// ## Example: Roll
 ///
 /// ```ignore
@@ -49,16 +92,13 @@ use syn::{
 /// #[args = "RollArgs"]
 /// #[description = "Roll a random number"]
 /// #[name = "roll_command"]
-/// #[options = "roll_options"]
 /// #[run = "my_roll_fn"]
 /// pub struct Roll;
 ///
-/// struct RollArgs { limit: u64 }
-///
-/// impl RollArgs {
-///     async fn parse_options(ctx: Arc<Context>, data: CommandData) -> BotResult<Self> {
-///         Ok(Self { limit: 100 })
-///     }
+/// #[derive(CommandArgs)]
+/// struct RollArgs {
+///     #[option(description = "Upper limit", min = 1, default = "100")]
+///     limit: u64,
 /// }
 ///
 /// async fn my_roll_fn(ctx: Arc<Context>, command: ApplicationCommand, args: RollArgs) -> BotResult<()> {
@@ -66,10 +106,6 @@ use syn::{
 ///
 ///     Ok(())
 /// }
-///
-/// fn roll_options() -> Vec<CommandOption> {
-///     Vec::new()
-/// }
 /// ```
 #[proc_macro_attribute]
 pub fn command(attr: TokenStream, input: TokenStream) -> TokenStream {
@@ -104,3 +140,63 @@ impl Parse for Base {
         Ok(Self { attributes, name })
     }
 }
+
+/// Generates `fn options() -> Vec<CommandOption>` and
+/// `async fn parse_options(Arc<Context>, CommandData) -> BotResult<Self>` for a struct.
+///
+/// Each field must implement `OptionValue` and be annotated with `#[option(...)]` which takes:
+/// - `description = "..."` (required) for the option's description.
+/// - `name = "..."` for the option name. Defaults to the field name.
+/// - `default = "..."` for an expression to use if the option was not specified.
+/// - `min = ...` and `max = ...` for the valid range of numbers.
+/// - `channel_types = "..."` for allowed `ChannelType`s of channel options, separated by `|`.
+///
+/// Fields of type `Option<T>` are optional, all other fields without default are required.
+///
+/// ## Example
+///
+/// ```ignore
+/// #[derive(CommandArgs)]
+/// pub struct SkipArgs {
+///     #[option(description = "Number of songs to skip", min = 1, default = "1")]
+///     amount: usize,
+///     #[option(description = "Only skip songs of this user")]
+///     user: Option<UserId>,
+/// }
+/// ```
+#[proc_macro_derive(CommandArgs, attributes(option))]
+pub fn command_args(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    match CommandArgs::try_from(input) {
+        Ok(args) => TokenStream::from(quote! { #args }),
+        Err(err) => TokenStream::from(err.to_compile_error()),
+    }
+}
+
+/// Implements `OptionValue` for an enum of unit variants so it can be used as string option with choices.
+///
+/// Variants can be annotated with `#[choice(name = "...", value = "...")]`.
+/// The name defaults to the variant name and the value to its lowercase version.
+///
+/// ## Example
+///
+/// ```ignore
+/// #[derive(CommandChoices)]
+/// pub enum LoopMode {
+///     #[choice(name = "Current track")]
+///     Track,
+///     Queue,
+///     #[choice(name = "Disable", value = "off")]
+///     Off,
+/// }
+/// ```
+#[proc_macro_derive(CommandChoices, attributes(choice))]
+pub fn command_choices(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    match CommandChoices::try_from(input) {
+        Ok(choices) => TokenStream::from(quote! { #choices }),
+        Err(err) => TokenStream::from(err.to_compile_error()),
+    }
+}
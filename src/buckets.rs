@@ -0,0 +1,349 @@
+use std::{
+    fmt,
+    str::FromStr,
+    sync::atomic::{AtomicU64, Ordering},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use dashmap::DashMap;
+use hashbrown::HashMap;
+use twilight_model::id::{GuildId, UserId};
+
+use crate::database::BucketOverride;
+
+/// Seconds between two sweeps over the entries to drop expired ones
+const PRUNE_INTERVAL: u64 = 600;
+
+/// Buckets that commands can be ratelimited by.
+///
+/// Commands sharing a named bucket also share their cooldowns,
+/// e.g. `/impersonate` and `/complete` both scan the message archive.
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub enum BucketName {
+    /// Commands that scan through the whole message archive
+    Archive,
+    /// Commands that call the paid Uberduck API
+    Tts,
+    /// Per-user cooldown for a single command, specified through `#[cooldown = "..."]`
+    Cooldown { command: &'static str, secs: u64 },
+}
+
+impl BucketName {
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Archive => "archive",
+            Self::Tts => "tts",
+            Self::Cooldown { command, .. } => command,
+        }
+    }
+
+    fn limits(self) -> BucketLimits {
+        match self {
+            Self::Archive => BucketLimits {
+                user: Some(Ratelimit::new(15, None)),
+                guild: Some(Ratelimit::new(3, None)),
+                global: None,
+            },
+            Self::Tts => BucketLimits {
+                user: Some(Ratelimit::new(20, Some((300, 5)))),
+                guild: Some(Ratelimit::new(5, Some((3600, 30)))),
+                global: Some(Ratelimit::new(0, Some((60, 10)))),
+            },
+            Self::Cooldown { secs, .. } => BucketLimits {
+                user: Some(Ratelimit::new(secs, None)),
+                guild: None,
+                global: None,
+            },
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub enum BucketScope {
+    User,
+    Guild,
+    Global,
+}
+
+impl fmt::Display for BucketScope {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::User => f.write_str("user"),
+            Self::Guild => f.write_str("guild"),
+            Self::Global => f.write_str("global"),
+        }
+    }
+}
+
+impl FromStr for BucketScope {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "user" => Ok(Self::User),
+            "guild" => Ok(Self::Guild),
+            "global" => Ok(Self::Global),
+            _ => Err(()),
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct Ratelimit {
+    /// Minimum amount of seconds between two uses
+    pub delay: u64,
+    /// At most `uses` within `window` seconds
+    pub limit: Option<(u64, u32)>,
+}
+
+impl Ratelimit {
+    pub const fn new(delay: u64, limit: Option<(u64, u32)>) -> Self {
+        Self { delay, limit }
+    }
+}
+
+#[derive(Copy, Clone)]
+struct BucketLimits {
+    user: Option<Ratelimit>,
+    guild: Option<Ratelimit>,
+    global: Option<Ratelimit>,
+}
+
+impl BucketLimits {
+    fn get_mut(&mut self, scope: BucketScope) -> &mut Option<Ratelimit> {
+        match scope {
+            BucketScope::User => &mut self.user,
+            BucketScope::Guild => &mut self.guild,
+            BucketScope::Global => &mut self.global,
+        }
+    }
+}
+
+#[derive(Default)]
+struct Entry {
+    last_time: u64,
+    set_time: u64,
+    tickets: u32,
+    /// Time from which on the entry no longer limits anything
+    expires: u64,
+}
+
+impl Entry {
+    /// Seconds until the entry can be used again
+    fn wait(&self, ratelimit: Ratelimit, now: u64) -> u64 {
+        if let Some((window, uses)) = ratelimit.limit {
+            let elapsed = now.saturating_sub(self.set_time);
+
+            if elapsed < window && self.tickets >= uses {
+                return window - elapsed;
+            }
+        }
+
+        (self.last_time + ratelimit.delay).saturating_sub(now)
+    }
+
+    fn take(&mut self, ratelimit: Ratelimit, now: u64) {
+        if let Some((window, _)) = ratelimit.limit {
+            if now.saturating_sub(self.set_time) >= window {
+                self.tickets = 0;
+                self.set_time = now;
+            }
+        }
+
+        self.tickets += 1;
+        self.last_time = now;
+
+        let window_end = ratelimit
+            .limit
+            .map_or(0, |(window, _)| self.set_time + window);
+
+        self.expires = (now + ratelimit.delay).max(window_end);
+    }
+}
+
+/// Keeps track of command usages and the per-guild overrides of bucket limits.
+pub struct Buckets {
+    entries: DashMap<(BucketName, BucketScope, u64), Entry>,
+    overrides: DashMap<GuildId, HashMap<String, HashMap<BucketScope, Ratelimit>>>,
+    last_prune: AtomicU64,
+}
+
+impl Buckets {
+    pub fn new(overrides: Vec<BucketOverride>) -> Self {
+        let buckets = Self {
+            entries: DashMap::new(),
+            overrides: DashMap::new(),
+            last_prune: AtomicU64::new(0),
+        };
+
+        for entry in overrides {
+            let BucketOverride {
+                guild_id,
+                bucket,
+                scope,
+                ratelimit,
+            } = entry;
+
+            if !buckets.set_override(guild_id, bucket.clone(), scope, ratelimit) {
+                warn!(
+                    "Rejected global override of bucket `{}` for guild {}",
+                    bucket, guild_id
+                );
+            }
+        }
+
+        buckets
+    }
+
+    /// Overrides the default ratelimit of a bucket's scope for the given guild.
+    /// Returns `false` for the global scope which is shared by all guilds and can't be overridden.
+    pub fn set_override(
+        &self,
+        guild_id: GuildId,
+        bucket: String,
+        scope: BucketScope,
+        ratelimit: Ratelimit,
+    ) -> bool {
+        if scope == BucketScope::Global {
+            return false;
+        }
+
+        self.overrides
+            .entry(guild_id)
+            .or_default()
+            .entry(bucket)
+            .or_default()
+            .insert(scope, ratelimit);
+
+        true
+    }
+
+    /// Checks every scope of the bucket and registers a usage if none of them are on cooldown.
+    /// Otherwise returns the scope that is on cooldown and the amount of seconds to wait.
+    pub fn take(
+        &self,
+        bucket: BucketName,
+        guild_id: Option<GuildId>,
+        user_id: UserId,
+    ) -> Option<(BucketScope, u64)> {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |duration| duration.as_secs());
+
+        self.prune(now);
+
+        let mut limits = bucket.limits();
+
+        if let Some(guild) = guild_id.and_then(|id| self.overrides.get(&id)) {
+            if let Some(overrides) = guild.get(bucket.name()) {
+                for (scope, ratelimit) in overrides {
+                    *limits.get_mut(*scope) = Some(*ratelimit);
+                }
+            }
+        }
+
+        let scopes = [
+            (BucketScope::User, limits.user, Some(user_id.0)),
+            (BucketScope::Guild, limits.guild, guild_id.map(|id| id.0)),
+            (BucketScope::Global, limits.global, Some(0)),
+        ];
+
+        let scopes = scopes
+            .iter()
+            .filter_map(|(scope, ratelimit, key)| Some((*scope, (*ratelimit)?, (*key)?)));
+
+        for (scope, ratelimit, key) in scopes.clone() {
+            if let Some(entry) = self.entries.get(&(bucket, scope, key)) {
+                let wait = entry.wait(ratelimit, now);
+
+                if wait > 0 {
+                    return Some((scope, wait));
+                }
+            }
+        }
+
+        for (scope, ratelimit, key) in scopes {
+            self.entries
+                .entry((bucket, scope, key))
+                .or_default()
+                .take(ratelimit, now);
+        }
+
+        None
+    }
+
+    /// Drops entries that no longer limit anything, at most once per `PRUNE_INTERVAL`
+    fn prune(&self, now: u64) {
+        let last_prune = self.last_prune.load(Ordering::Relaxed);
+
+        if now < last_prune + PRUNE_INTERVAL {
+            return;
+        }
+
+        // Only one caller gets to sweep
+        let swept =
+            self.last_prune
+                .compare_exchange(last_prune, now, Ordering::Relaxed, Ordering::Relaxed);
+
+        if swept.is_ok() {
+            self.entries.retain(|_, entry| entry.expires > now);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_entry_delay() {
+        let ratelimit = Ratelimit::new(10, None);
+        let mut entry = Entry::default();
+
+        assert_eq!(entry.wait(ratelimit, 100), 0);
+        entry.take(ratelimit, 100);
+        assert_eq!(entry.wait(ratelimit, 104), 6);
+        assert_eq!(entry.wait(ratelimit, 110), 0);
+    }
+
+    #[test]
+    fn test_entry_limit() {
+        let ratelimit = Ratelimit::new(0, Some((60, 2)));
+        let mut entry = Entry::default();
+
+        entry.take(ratelimit, 100);
+        entry.take(ratelimit, 101);
+        assert_eq!(entry.wait(ratelimit, 120), 40);
+        assert_eq!(entry.wait(ratelimit, 160), 0);
+    }
+
+    #[test]
+    fn test_clock_going_backwards() {
+        let ratelimit = Ratelimit::new(0, Some((60, 1)));
+        let mut entry = Entry::default();
+
+        entry.take(ratelimit, 100);
+        assert_eq!(entry.wait(ratelimit, 90), 60);
+        entry.take(ratelimit, 90);
+    }
+
+    #[test]
+    fn test_prune_expired() {
+        let buckets = Buckets::new(Vec::new());
+        let bucket = BucketName::Cooldown {
+            command: "test",
+            secs: 10,
+        };
+
+        assert!(buckets.take(bucket, None, UserId(1)).is_none());
+        assert_eq!(buckets.entries.len(), 1);
+
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+
+        buckets.prune(now + PRUNE_INTERVAL + 10);
+        assert!(buckets.entries.is_empty());
+    }
+}
@@ -0,0 +1,269 @@
+use std::convert::TryFrom;
+
+use twilight_model::{
+    application::{
+        command::{
+            BaseCommandOptionData, ChannelCommandOptionData, ChoiceCommandOptionData, CommandOption,
+        },
+        interaction::application_command::{
+            CommandDataOption, CommandInteractionDataResolved, InteractionChannel,
+        },
+    },
+    channel::ChannelType,
+    id::{ChannelId, RoleId, UserId},
+};
+
+use crate::error::ArgsError;
+
+/// Everything that's known about an option through its `#[option(...)]` attribute.
+pub struct OptionInfo {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub required: bool,
+    pub min: Option<i64>,
+    pub max: Option<i64>,
+    pub channel_types: &'static [ChannelType],
+}
+
+/// Types that can be used as fields of a `#[derive(CommandArgs)]` struct.
+pub trait OptionValue: Sized {
+    fn create_option(info: &OptionInfo) -> CommandOption;
+
+    fn parse_option(
+        info: &OptionInfo,
+        option: &CommandDataOption,
+        resolved: Option<&CommandInteractionDataResolved>,
+    ) -> Result<Self, ArgsError>;
+}
+
+pub fn option_name(option: &CommandDataOption) -> &str {
+    match option {
+        CommandDataOption::String { name, .. }
+        | CommandDataOption::Integer { name, .. }
+        | CommandDataOption::Boolean { name, .. }
+        | CommandDataOption::SubCommand { name, .. } => name,
+    }
+}
+
+fn base_data(info: &OptionInfo) -> BaseCommandOptionData {
+    BaseCommandOptionData {
+        description: info.description.to_owned(),
+        name: info.name.to_owned(),
+        required: info.required,
+    }
+}
+
+fn choice_data(info: &OptionInfo) -> ChoiceCommandOptionData {
+    ChoiceCommandOptionData {
+        choices: Vec::new(),
+        description: info.description.to_owned(),
+        name: info.name.to_owned(),
+        required: info.required,
+    }
+}
+
+fn string_value<'o>(
+    info: &OptionInfo,
+    option: &'o CommandDataOption,
+) -> Result<&'o str, ArgsError> {
+    match option {
+        CommandDataOption::String { value, .. } => Ok(value),
+        _ => Err(ArgsError::InvalidType { name: info.name }),
+    }
+}
+
+fn check_range(info: &OptionInfo, value: i64) -> Result<i64, ArgsError> {
+    let too_small = info.min.map_or(false, |min| value < min);
+    let too_large = info.max.map_or(false, |max| value > max);
+
+    if too_small || too_large {
+        Err(ArgsError::OutOfRange {
+            name: info.name,
+            value: value.to_string(),
+            min: info.min,
+            max: info.max,
+        })
+    } else {
+        Ok(value)
+    }
+}
+
+impl OptionValue for String {
+    fn create_option(info: &OptionInfo) -> CommandOption {
+        CommandOption::String(choice_data(info))
+    }
+
+    fn parse_option(
+        info: &OptionInfo,
+        option: &CommandDataOption,
+        _: Option<&CommandInteractionDataResolved>,
+    ) -> Result<Self, ArgsError> {
+        string_value(info, option).map(str::to_owned)
+    }
+}
+
+impl OptionValue for bool {
+    fn create_option(info: &OptionInfo) -> CommandOption {
+        CommandOption::Boolean(base_data(info))
+    }
+
+    fn parse_option(
+        info: &OptionInfo,
+        option: &CommandDataOption,
+        _: Option<&CommandInteractionDataResolved>,
+    ) -> Result<Self, ArgsError> {
+        match option {
+            CommandDataOption::Boolean { value, .. } => Ok(*value),
+            _ => Err(ArgsError::InvalidType { name: info.name }),
+        }
+    }
+}
+
+macro_rules! impl_integer {
+    ($($ty:ty),*) => {
+        $(
+            impl OptionValue for $ty {
+                fn create_option(info: &OptionInfo) -> CommandOption {
+                    CommandOption::Integer(choice_data(info))
+                }
+
+                fn parse_option(
+                    info: &OptionInfo,
+                    option: &CommandDataOption,
+                    _: Option<&CommandInteractionDataResolved>,
+                ) -> Result<Self, ArgsError> {
+                    let value = match option {
+                        CommandDataOption::Integer { value, .. } => check_range(info, *value)?,
+                        _ => return Err(ArgsError::InvalidType { name: info.name }),
+                    };
+
+                    <$ty>::try_from(value).map_err(|_| ArgsError::OutOfRange {
+                        name: info.name,
+                        value: value.to_string(),
+                        min: info.min,
+                        max: info.max,
+                    })
+                }
+            }
+        )*
+    };
+}
+
+impl_integer!(i32, i64, u32, u64, usize);
+
+// Discord's number option type isn't supported yet
+// so floats are passed as strings and parsed manually
+macro_rules! impl_float {
+    ($($ty:ty),*) => {
+        $(
+            impl OptionValue for $ty {
+                fn create_option(info: &OptionInfo) -> CommandOption {
+                    CommandOption::String(choice_data(info))
+                }
+
+                fn parse_option(
+                    info: &OptionInfo,
+                    option: &CommandDataOption,
+                    _: Option<&CommandInteractionDataResolved>,
+                ) -> Result<Self, ArgsError> {
+                    let value = string_value(info, option)?;
+
+                    let parsed = value.trim().parse::<$ty>().map_err(|_| ArgsError::Parse {
+                        name: info.name,
+                        value: value.to_owned(),
+                    })?;
+
+                    let too_small = info.min.map_or(false, |min| parsed < min as $ty);
+                    let too_large = info.max.map_or(false, |max| parsed > max as $ty);
+
+                    if too_small || too_large || !parsed.is_finite() {
+                        return Err(ArgsError::OutOfRange {
+                            name: info.name,
+                            value: value.to_owned(),
+                            min: info.min,
+                            max: info.max,
+                        });
+                    }
+
+                    Ok(parsed)
+                }
+            }
+        )*
+    };
+}
+
+impl_float!(f32, f64);
+
+macro_rules! impl_id {
+    ($ty:ident, $variant:ident) => {
+        impl OptionValue for $ty {
+            fn create_option(info: &OptionInfo) -> CommandOption {
+                CommandOption::$variant(base_data(info))
+            }
+
+            fn parse_option(
+                info: &OptionInfo,
+                option: &CommandDataOption,
+                _: Option<&CommandInteractionDataResolved>,
+            ) -> Result<Self, ArgsError> {
+                let value = string_value(info, option)?;
+
+                value.parse().map($ty).map_err(|_| ArgsError::Parse {
+                    name: info.name,
+                    value: value.to_owned(),
+                })
+            }
+        }
+    };
+}
+
+impl_id!(UserId, User);
+impl_id!(RoleId, Role);
+
+impl OptionValue for ChannelId {
+    fn create_option(info: &OptionInfo) -> CommandOption {
+        CommandOption::Channel(ChannelCommandOptionData {
+            channel_types: info.channel_types.to_vec(),
+            description: info.description.to_owned(),
+            name: info.name.to_owned(),
+            required: info.required,
+        })
+    }
+
+    fn parse_option(
+        info: &OptionInfo,
+        option: &CommandDataOption,
+        _: Option<&CommandInteractionDataResolved>,
+    ) -> Result<Self, ArgsError> {
+        let value = string_value(info, option)?;
+
+        value.parse().map(ChannelId).map_err(|_| ArgsError::Parse {
+            name: info.name,
+            value: value.to_owned(),
+        })
+    }
+}
+
+impl OptionValue for InteractionChannel {
+    fn create_option(info: &OptionInfo) -> CommandOption {
+        ChannelId::create_option(info)
+    }
+
+    fn parse_option(
+        info: &OptionInfo,
+        option: &CommandDataOption,
+        resolved: Option<&CommandInteractionDataResolved>,
+    ) -> Result<Self, ArgsError> {
+        let channel_id = ChannelId::parse_option(info, option, resolved)?;
+
+        resolved
+            .and_then(|resolved| {
+                resolved
+                    .channels
+                    .iter()
+                    .find(|channel| channel.id == channel_id)
+            })
+            .cloned()
+            .ok_or(ArgsError::Unresolved { name: info.name })
+    }
+}
@@ -0,0 +1,75 @@
+use twilight_model::{
+    application::interaction::ApplicationCommand, guild::Permissions, id::UserId,
+};
+
+use crate::{
+    context::Context,
+    error::BotResult,
+    utils::{ApplicationCommandExt, OWNER_USER_ID},
+};
+
+/// Preconditions of a command, specified through attributes of the `#[command]` macro.
+pub struct CommandChecks {
+    pub guild_only: bool,
+    pub owner_only: bool,
+    pub in_voice: bool,
+    pub permissions: Option<Permissions>,
+}
+
+impl CommandChecks {
+    /// Returns the reason why the author can't use the command, if any.
+    pub fn check(&self, ctx: &Context, command: &ApplicationCommand) -> BotResult<Option<String>> {
+        let author_id = command.user_id()?;
+
+        if self.owner_only && author_id != UserId(OWNER_USER_ID) {
+            return Ok(Some(
+                "This command can only be used by the bot owner!".to_owned(),
+            ));
+        }
+
+        let guild_id = match command.guild_id {
+            Some(id) => id,
+            None if self.guild_only => {
+                return Ok(Some(
+                    "This command can only be used in a server!".to_owned(),
+                ))
+            }
+            None => return Ok(None),
+        };
+
+        if let Some(required) = self.permissions {
+            let permissions = command
+                .member
+                .as_ref()
+                .and_then(|member| member.permissions)
+                .unwrap_or_else(Permissions::empty);
+
+            if !permissions.contains(Permissions::ADMINISTRATOR) {
+                let missing = required - permissions;
+
+                if !missing.is_empty() {
+                    let content = format!(
+                        "You need the following permissions to use this command: `{:?}`",
+                        missing
+                    );
+
+                    return Ok(Some(content));
+                }
+            }
+        }
+
+        if self.in_voice {
+            let in_voice = ctx
+                .cache
+                .voice_state(author_id, guild_id)
+                .and_then(|state| state.channel_id)
+                .is_some();
+
+            if !in_voice {
+                return Ok(Some("You aren't in a voice channel!".to_owned()));
+            }
+        }
+
+        Ok(None)
+    }
+}
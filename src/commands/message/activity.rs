@@ -1,13 +1,7 @@
 use std::{borrow::Cow, sync::Arc};
 
 use twilight_model::{
-    application::{
-        command::{ChannelCommandOptionData, CommandOption},
-        interaction::{
-            application_command::{CommandData, InteractionChannel},
-            ApplicationCommand,
-        },
-    },
+    application::interaction::{application_command::InteractionChannel, ApplicationCommand},
     channel::ChannelType,
 };
 
@@ -22,32 +16,20 @@ use crate::{
 
 #[command]
 #[args = "ActivityArgs"]
+#[cooldown = "5"]
 #[description = "Get the server activity for the last month, week, day and hour"]
-#[options = "activity_options"]
+#[guild_only]
 pub struct Activity;
 
+#[derive(CommandArgs)]
 pub struct ActivityArgs {
+    #[option(
+        description = "Specify an optional channel to check the activity for",
+        channel_types = "GuildText"
+    )]
     channel: Option<InteractionChannel>,
 }
 
-impl ActivityArgs {
-    async fn parse_options(_: Arc<Context>, data: CommandData) -> BotResult<Self> {
-        let channel = data.resolved.and_then(|mut data| data.channels.pop());
-        Ok(Self { channel })
-    }
-}
-
-fn activity_options() -> Vec<CommandOption> {
-    let option_data = ChannelCommandOptionData {
-        channel_types: vec![ChannelType::GuildText],
-        description: "Specify an optional channel to check the activity for".to_string(),
-        name: "channel".to_string(),
-        required: false,
-    };
-
-    vec![CommandOption::Channel(option_data)]
-}
-
 async fn activity(
     ctx: Arc<Context>,
     command: ApplicationCommand,
@@ -58,13 +40,7 @@ async fn activity(
         .as_ref()
         .filter(|channel| channel.kind != ChannelType::GuildText);
 
-    let guild_id = if let Some(id) = command.guild_id {
-        id
-    } else {
-        let builder = MessageBuilder::new().error("This command can only be used in a server!");
-        command.create_message(&ctx, builder).await?;
-        return Ok(());
-    };
+    let guild_id = command.require_guild_id()?;
 
     let is_channel = args.channel.is_some();
 
@@ -74,10 +50,7 @@ async fn activity(
     } else {
         let activity = ctx
             .database
-            .get_activity(
-                command.guild_id.unwrap(),
-                args.channel.as_ref().map(|c| c.id),
-            )
+            .get_activity(guild_id, args.channel.as_ref().map(|c| c.id))
             .await?;
 
         let name = args
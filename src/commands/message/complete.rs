@@ -3,18 +3,9 @@ use std::{borrow::Cow, sync::Arc};
 
 use cow_utils::CowUtils;
 use markov::Chain;
-use twilight_model::id::{ChannelId, UserId};
 use twilight_model::{
-    application::{
-        command::{
-            BaseCommandOptionData, ChannelCommandOptionData, ChoiceCommandOptionData, CommandOption,
-        },
-        interaction::{
-            application_command::{CommandData, CommandDataOption, InteractionChannel},
-            ApplicationCommand,
-        },
-    },
-    channel::ChannelType,
+    application::interaction::ApplicationCommand,
+    id::{ChannelId, UserId},
 };
 
 use crate::{
@@ -28,71 +19,24 @@ use crate::{
 
 #[command]
 #[args = "CompleteArgs"]
+#[bucket = "Archive"]
 #[description = "Finish the given sentence based on previous message data"]
-#[options = "complete_options"]
+#[guild_only]
 pub struct Complete;
 
+#[derive(CommandArgs)]
 pub struct CompleteArgs {
+    #[option(description = "Specify an optional user to take message data from")]
     author: Option<UserId>,
+    #[option(
+        description = "Specify an optional channel to take message data from",
+        channel_types = "GuildText"
+    )]
     channel: Option<ChannelId>,
+    #[option(description = "Specify the sentence to complete")]
     contains: String,
 }
 
-impl CompleteArgs {
-    async fn parse_options(_: Arc<Context>, data: CommandData) -> BotResult<Self> {
-        let author = data
-            .resolved
-            .as_ref()
-            .and_then(|data| data.members.last().map(|c| c.id));
-        let channel = data
-            .resolved
-            .as_ref()
-            .and_then(|data| data.channels.last().map(|c| c.id));
-
-        for option in data.options {
-            if let CommandDataOption::String { name, value } = option {
-                if name == "contains" {
-                    return Ok(Self {
-                        author,
-                        channel,
-                        contains: value,
-                    });
-                }
-            }
-        }
-
-        unreachable!()
-    }
-}
-
-fn complete_options() -> Vec<CommandOption> {
-    let author = BaseCommandOptionData {
-        description: "Specify an optional user to take message data from".to_string(),
-        name: "author".to_string(),
-        required: false,
-    };
-
-    let channel = ChannelCommandOptionData {
-        channel_types: vec![ChannelType::GuildText],
-        description: "Specify an optional channel to take message data from".to_string(),
-        name: "channel".to_string(),
-        required: false,
-    };
-
-    let contains = ChoiceCommandOptionData {
-        choices: Vec::new(),
-        description: "Specify the sentence to complete".to_string(),
-        name: "contains".to_string(),
-        required: true,
-    };
-
-    vec![
-        CommandOption::String(contains),
-        CommandOption::User(author),
-        CommandOption::Channel(channel),
-    ]
-}
-
 async fn complete(
     ctx: Arc<Context>,
     command: ApplicationCommand,
@@ -106,7 +50,7 @@ async fn complete(
             args.author,
             args.channel,
             &args.contains,
-            command.guild_id.unwrap(),
+            command.require_guild_id()?,
         )
         .await?;
 
@@ -3,18 +3,9 @@ use std::{borrow::Cow, sync::Arc};
 
 use cow_utils::CowUtils;
 use markov::Chain;
-use twilight_model::id::{ChannelId, UserId};
 use twilight_model::{
-    application::{
-        command::{
-            BaseCommandOptionData, ChannelCommandOptionData, ChoiceCommandOptionData, CommandOption,
-        },
-        interaction::{
-            application_command::{CommandData, CommandDataOption, InteractionChannel},
-            ApplicationCommand,
-        },
-    },
-    channel::ChannelType,
+    application::interaction::ApplicationCommand,
+    id::{ChannelId, UserId},
 };
 
 use crate::{
@@ -28,47 +19,22 @@ use crate::{
 
 #[command]
 #[args = "ImpersonateArgs"]
+#[bucket = "Archive"]
 #[description = "Impersonate a user or channel based on previous message data"]
-#[options = "impersonate_options"]
+#[guild_only]
 pub struct Impersonate;
 
+#[derive(CommandArgs)]
 pub struct ImpersonateArgs {
+    #[option(description = "Specify an optional user to take message data from")]
     author: Option<UserId>,
+    #[option(
+        description = "Specify an optional channel to take message data from",
+        channel_types = "GuildText"
+    )]
     channel: Option<ChannelId>,
 }
 
-impl ImpersonateArgs {
-    async fn parse_options(_: Arc<Context>, data: CommandData) -> BotResult<Self> {
-        let author = data
-            .resolved
-            .as_ref()
-            .and_then(|data| data.members.last().map(|c| c.id));
-        let channel = data
-            .resolved
-            .as_ref()
-            .and_then(|data| data.channels.last().map(|c| c.id));
-
-        return Ok(Self { author, channel });
-    }
-}
-
-fn impersonate_options() -> Vec<CommandOption> {
-    let author = BaseCommandOptionData {
-        description: "Specify an optional user to take message data from".to_string(),
-        name: "author".to_string(),
-        required: false,
-    };
-
-    let channel = ChannelCommandOptionData {
-        channel_types: vec![ChannelType::GuildText],
-        description: "Specify an optional channel to take message data from".to_string(),
-        name: "channel".to_string(),
-        required: false,
-    };
-
-    vec![CommandOption::User(author), CommandOption::Channel(channel)]
-}
-
 async fn impersonate(
     ctx: Arc<Context>,
     command: ApplicationCommand,
@@ -78,7 +44,7 @@ async fn impersonate(
 
     let strings = ctx
         .database
-        .get_messages(args.author, args.channel, command.guild_id.unwrap())
+        .get_messages(args.author, args.channel, command.require_guild_id()?)
         .await?;
 
     if strings.is_empty() {
@@ -1,45 +1,106 @@
+mod args;
+mod checks;
 mod message;
 mod music;
 mod osu;
+mod report;
 mod utils;
 
 use std::sync::Arc;
 
 use message::Activity;
-use music::Clear;
-use twilight_model::application::{command::Command, interaction::ApplicationCommand};
-use utils::{Ping, Roll};
+use music::{Autoplay, Library, Music, Playlists, NOW_PLAYING_PREFIX, SEARCH_PICK, VOTE_SKIP};
+use twilight_model::application::{
+    command::Command,
+    interaction::{
+        application_command::CommandDataOption, ApplicationCommand, MessageComponentInteraction,
+    },
+};
+use utils::{Cooldown, Ping, Roll};
 
 use crate::{
+    buckets::{BucketName, BucketScope},
     commands::{
         message::{Complete, Impersonate},
         osu::Suijisim,
     },
     context::Context,
     error::{BotResult, Error},
-    utils::ApplicationCommandExt,
+    pagination::{self, PAGINATION_PREFIX},
+    utils::{ApplicationCommandExt, MessageBuilder},
 };
+pub use args::{option_name, OptionInfo, OptionValue};
+pub use checks::CommandChecks;
 pub use message::MessageActivity;
+pub use music::{
+    check_idle, save_queue, AudioFilter, IdleTracker, LoopMode, NowPlayingPanel, PlayedTrack,
+    SkipVote,
+};
+use report::CommandReport;
+
+/// Generates `twilight_commands`, `command_bucket`, `command_check`, and `run_command`
+/// for the given top-level commands
+macro_rules! register_commands {
+    ($($command:ident),* $(,)?) => {
+        pub fn twilight_commands() -> Vec<Command> {
+            vec![$($command::define(),)*]
+        }
+
+        fn command_bucket(command: &ApplicationCommand) -> Option<BucketName> {
+            match command.data.name.as_str() {
+                $($command::NAME => $command::bucket(&command.data.options),)*
+                _ => None,
+            }
+        }
+
+        fn command_check(ctx: &Context, command: &ApplicationCommand) -> BotResult<Option<String>> {
+            match command.data.name.as_str() {
+                $($command::NAME => $command::check(ctx, command, &command.data.options),)*
+                _ => Ok(None),
+            }
+        }
+
+        async fn run_command(ctx: Arc<Context>, command: ApplicationCommand) -> BotResult<()> {
+            match command.data.name.as_str() {
+                $($command::NAME => $command::run(ctx, command).await,)*
+                _ => Err(Error::UnknownInteraction {
+                    command: Box::new(command),
+                }),
+            }
+        }
+    };
+}
 
-use self::music::{Pause, Play, Queue, Skip, Stop, Tts, Volume};
-
-pub fn twilight_commands() -> Vec<Command> {
-    vec![
-        Clear::define(),
-        Complete::define(),
-        Impersonate::define(),
-        Pause::define(),
-        Ping::define(),
-        Play::define(),
-        Queue::define(),
-        Skip::define(),
-        Stop::define(),
-        Suijisim::define(),
-        Tts::define(),
-        Volume::define(),
-        Roll::define(),
-        Activity::define(),
-    ]
+register_commands![
+    Activity,
+    Autoplay,
+    Complete,
+    Cooldown,
+    Impersonate,
+    Library,
+    Music,
+    Ping,
+    Playlists,
+    Roll,
+    Suijisim,
+];
+
+/// Full name of the invoked command including its subcommands e.g. `music queue show`
+fn command_path(command: &ApplicationCommand) -> String {
+    let mut path = command.data.name.clone();
+    let mut options = command.data.options.as_slice();
+
+    while let Some(CommandDataOption::SubCommand {
+        name,
+        options: next,
+    }) = options.first()
+    {
+        path.push(' ');
+        path.push_str(name);
+        options = next.as_slice();
+    }
+
+    path
 }
 
 fn log_slash(ctx: &Context, command: &ApplicationCommand, cmd_name: &str) {
@@ -63,27 +124,87 @@ fn log_slash(ctx: &Context, command: &ApplicationCommand, cmd_name: &str) {
 }
 
 pub async fn handle_interaction(ctx: Arc<Context>, command: ApplicationCommand) -> BotResult<()> {
-    let name = command.data.name.as_str();
-    log_slash(&ctx, &command, name);
-    ctx.stats.increment_slash_command(name);
-
-    match name {
-        Activity::NAME => Activity::run(ctx, command).await,
-        Clear::NAME => Clear::run(ctx, command).await,
-        Complete::NAME => Complete::run(ctx, command).await,
-        Impersonate::NAME => Impersonate::run(ctx, command).await,
-        Pause::NAME => Pause::run(ctx, command).await,
-        Ping::NAME => Ping::run(ctx, command).await,
-        Play::NAME => Play::run(ctx, command).await,
-        Queue::NAME => Queue::run(ctx, command).await,
-        Skip::NAME => Skip::run(ctx, command).await,
-        Stop::NAME => Stop::run(ctx, command).await,
-        Suijisim::NAME => Suijisim::run(ctx, command).await,
-        Tts::NAME => Tts::run(ctx, command).await,
-        Volume::NAME => Volume::run(ctx, command).await,
-        Roll::NAME => Roll::run(ctx, command).await,
-        _ => Err(Error::UnknownInteraction {
-            command: Box::new(command),
-        }),
+    let path = command_path(&command);
+    log_slash(&ctx, &command, &path);
+    ctx.stats.increment_slash_command(&path);
+
+    // Failed checks shouldn't use up the author's ratelimit
+    if let Some(content) = command_check(&ctx, &command)? {
+        let builder = MessageBuilder::new().error(content).ephemeral(true);
+        return command.create_message(&ctx, builder).await;
     }
+
+    if let Some(bucket) = command_bucket(&command) {
+        if check_ratelimit(&ctx, &command, bucket).await? {
+            return Ok(());
+        }
+    }
+
+    let report = CommandReport::new(&command, path);
+
+    if let Err(why) = run_command(Arc::clone(&ctx), command).await {
+        report.report(&ctx, why).await;
+    }
+
+    Ok(())
+}
+
+pub async fn handle_component(
+    ctx: Arc<Context>,
+    component: MessageComponentInteraction,
+) -> BotResult<()> {
+    let custom_id = component.data.custom_id.as_str();
+
+    if custom_id.starts_with(PAGINATION_PREFIX) {
+        pagination::handle_component(ctx, component).await
+    } else if custom_id.starts_with(NOW_PLAYING_PREFIX) {
+        music::handle_now_playing(ctx, component).await
+    } else if custom_id == VOTE_SKIP {
+        music::handle_vote(ctx, component).await
+    } else if custom_id == SEARCH_PICK {
+        // Handled by whoever waits for it through standby
+        Ok(())
+    } else {
+        warn!("Received unknown component interaction `{}`", custom_id);
+
+        Ok(())
+    }
+}
+
+/// Returns `true` if the command is on cooldown, in which case the user has already been notified.
+async fn check_ratelimit(
+    ctx: &Context,
+    command: &ApplicationCommand,
+    bucket: BucketName,
+) -> BotResult<bool> {
+    let user_id = command.user_id()?;
+
+    let (scope, wait) = match ctx.buckets.take(bucket, command.guild_id, user_id) {
+        Some(ratelimited) => ratelimited,
+        None => return Ok(false),
+    };
+
+    info!(
+        "Ratelimited {} on bucket `{}` ({} scope) for {}s",
+        command.username()?,
+        bucket.name(),
+        scope,
+        wait
+    );
+
+    let content = format!(
+        "{} on cooldown, try again in {} second{}",
+        match scope {
+            BucketScope::User => "You are",
+            BucketScope::Guild => "This command is",
+            BucketScope::Global => "This command is globally",
+        },
+        wait,
+        if wait == 1 { "" } else { "s" }
+    );
+
+    let builder = MessageBuilder::new().error(content).ephemeral(true);
+    command.create_message(ctx, builder).await?;
+
+    Ok(true)
 }
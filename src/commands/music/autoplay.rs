@@ -0,0 +1,249 @@
+use std::{collections::HashSet, sync::Arc};
+
+use rand::seq::SliceRandom;
+use songbird::tracks::TrackHandle;
+use twilight_model::{application::interaction::ApplicationCommand, id::GuildId};
+
+use crate::{
+    commands::music::{enqueue, is_dj, requester, song_source},
+    context::Context,
+    database::{MusicSettings, PlaylistOwner},
+    error::BotResult,
+    utils::{ApplicationCommandExt, MessageBuilder},
+};
+
+/// How many related songs are looked up per kind of relation
+const RELATED_LIMIT: usize = 10;
+/// Autoplay picks randomly among this many of the most related songs
+const RELATED_CHOICES: usize = 3;
+
+#[command]
+#[args = "AutoplayArgs"]
+#[description = "Keep playing related songs once the queue runs out"]
+#[guild_only]
+pub struct Autoplay;
+
+#[derive(CommandArgs)]
+pub struct AutoplayArgs {
+    #[option(description = "Specify whether autoplay is on, toggles it if no option is given")]
+    enabled: Option<bool>,
+    #[option(
+        description = "Specify a server playlist to shuffle from if nothing related is found, `none` to remove it"
+    )]
+    playlist: Option<String>,
+    #[option(
+        description = "Specify how many of the latest songs may not be played again",
+        min = 0,
+        max = 100
+    )]
+    no_repeat: Option<u32>,
+}
+
+pub async fn autoplay(
+    ctx: Arc<Context>,
+    command: ApplicationCommand,
+    args: AutoplayArgs,
+) -> BotResult<()> {
+    let AutoplayArgs {
+        enabled,
+        playlist,
+        no_repeat,
+    } = args;
+
+    let guild_id = command.require_guild_id()?;
+
+    if !is_dj(&ctx, command.member.as_ref()) {
+        let builder = MessageBuilder::new().error("You need the DJ role to change autoplay!");
+        return command.create_message(&ctx, builder).await;
+    }
+
+    let mut settings = ctx.guild_music_settings(guild_id);
+    let toggle = enabled.is_none() && playlist.is_none() && no_repeat.is_none();
+
+    settings.autoplay = match enabled {
+        Some(enabled) => enabled,
+        None if toggle => !settings.autoplay,
+        None => settings.autoplay,
+    };
+
+    if let Some(name) = playlist {
+        if name.eq_ignore_ascii_case("none") {
+            settings.autoplay_playlist = None;
+        } else {
+            let owner = PlaylistOwner::Guild(guild_id);
+
+            match ctx.database.get_playlist(owner, &name).await? {
+                Some(playlist) => settings.autoplay_playlist = Some(playlist.name),
+                None => {
+                    let content = format!("There is no server playlist **{}**!", name);
+                    let builder = MessageBuilder::new().error(content);
+                    return command.create_message(&ctx, builder).await;
+                }
+            }
+        }
+    }
+
+    if let Some(amount) = no_repeat {
+        settings.autoplay_no_repeat = amount.min(100) as u16;
+    }
+
+    info!("Updating autoplay of guild {}...", guild_id);
+
+    ctx.database
+        .upsert_music_settings(guild_id, &settings)
+        .await?;
+
+    ctx.music_settings.insert(guild_id, settings.clone());
+
+    let content = if settings.autoplay {
+        let fallback = match settings.autoplay_playlist {
+            Some(ref name) => format!(
+                "If nothing related is found, I shuffle the server playlist **{}**",
+                name
+            ),
+            None => "If nothing related is found, the music stops".to_owned(),
+        };
+
+        format!(
+            "Autoplay is **on**!\n\
+            Once the queue runs out I add songs related to this server's history\n{}\n\
+            Songs of the latest {} plays aren't repeated",
+            fallback, settings.autoplay_no_repeat
+        )
+    } else {
+        "Autoplay is **off**!".to_owned()
+    };
+
+    let builder = MessageBuilder::new().embed(content);
+    command.create_message(&ctx, builder).await
+}
+
+/// Add a song related to the finished one if autoplay is on and the queue ran out
+pub async fn autoplay_next(ctx: Arc<Context>, guild_id: GuildId, finished: TrackHandle) {
+    let settings = ctx.guild_music_settings(guild_id);
+
+    if !settings.autoplay {
+        return;
+    }
+
+    let call_lock = match ctx.songbird.get(guild_id) {
+        Some(call) => call,
+        None => return,
+    };
+
+    // The finished song might not be removed from the queue yet
+    let ran_out =
+        |queue: Vec<TrackHandle>| queue.iter().all(|handle| handle.uuid() == finished.uuid());
+
+    if !ran_out(call_lock.lock().await.queue().current_queue()) {
+        return;
+    }
+
+    let requester = match requester(&finished).await {
+        Some(requester) => requester,
+        None => return,
+    };
+
+    let url = match pick_song(&ctx, guild_id, &finished, &settings).await {
+        Ok(Some(url)) => url,
+        Ok(None) => {
+            info!("Found no song to autoplay in guild {}", guild_id);
+            return;
+        }
+        Err(why) => {
+            unwind_error!(warn, why, "Failed to pick a song to autoplay: {}");
+            return;
+        }
+    };
+
+    let song = match song_source(&ctx, guild_id, url.clone()).await {
+        Ok(song) => song,
+        Err(why) => {
+            unwind_error!(warn, why, "Failed to load song {} to autoplay: {}", url);
+            return;
+        }
+    };
+
+    let mut call = call_lock.lock().await;
+
+    // Someone might have added a song or stopped the music in the meantime
+    if call.current_channel().is_none() || !ran_out(call.queue().current_queue()) {
+        return;
+    }
+
+    info!("Autoplaying {} in guild {}...", url, guild_id);
+
+    if let Err(why) = enqueue(&ctx, &mut call, song, requester).await {
+        unwind_error!(warn, why, "Failed to autoplay song: {}");
+    }
+}
+
+/// Songs played after the finished one or by the same artist are preferred,
+/// the fallback playlist is only used if the history has nothing related
+async fn pick_song(
+    ctx: &Context,
+    guild_id: GuildId,
+    finished: &TrackHandle,
+    settings: &MusicSettings,
+) -> BotResult<Option<String>> {
+    let no_repeat = settings.autoplay_no_repeat as usize;
+
+    let recent: HashSet<_> = ctx
+        .database
+        .get_recent_history(guild_id, no_repeat)
+        .await?
+        .into_iter()
+        .map(|entry| entry.url)
+        .collect();
+
+    let metadata = finished.metadata();
+    let mut related = Vec::new();
+
+    if let Some(ref url) = metadata.source_url {
+        let coplayed = ctx
+            .database
+            .get_coplayed_songs(guild_id, url, RELATED_LIMIT)
+            .await?;
+
+        related.extend(coplayed);
+
+        if let Some(ref artist) = metadata.artist {
+            let same_artist = ctx
+                .database
+                .get_artist_songs(guild_id, artist, url, RELATED_LIMIT)
+                .await?;
+
+            related.extend(same_artist);
+        }
+    }
+
+    related.retain(|url| !recent.contains(url));
+    related.truncate(RELATED_CHOICES);
+
+    if let Some(url) = related.choose(&mut rand::thread_rng()) {
+        return Ok(Some(url.clone()));
+    }
+
+    let name = match settings.autoplay_playlist {
+        Some(ref name) => name,
+        None => return Ok(None),
+    };
+
+    let owner = PlaylistOwner::Guild(guild_id);
+
+    let playlist = match ctx.database.get_playlist(owner, name).await? {
+        Some(playlist) => playlist,
+        None => return Ok(None),
+    };
+
+    let candidates: Vec<_> = ctx
+        .database
+        .get_playlist_tracks(playlist.id)
+        .await?
+        .into_iter()
+        .map(|track| track.url)
+        .filter(|url| !recent.contains(url))
+        .collect();
+
+    Ok(candidates.choose(&mut rand::thread_rng()).cloned())
+}
@@ -0,0 +1,188 @@
+use std::sync::Arc;
+
+use twilight_model::{application::interaction::ApplicationCommand, id::GuildId};
+
+use crate::{
+    commands::music::{ensure_same_channel, is_dj, require_call},
+    context::Context,
+    error::BotResult,
+    utils::{ApplicationCommandExt, MessageBuilder},
+};
+
+#[command]
+#[args = "FilterArgs"]
+#[description = "Apply an audio effect to the current and upcoming songs"]
+#[in_voice]
+pub struct Filter;
+
+#[derive(CommandArgs)]
+pub struct FilterArgs {
+    #[option(description = "Specify the effect to apply")]
+    effect: FilterKind,
+    #[option(
+        description = "Specify the factor for speed and pitch between 0.5 and 2 (can be decimal)",
+        min = 0,
+        max = 2
+    )]
+    factor: Option<f32>,
+}
+
+#[derive(Copy, Clone, CommandChoices, Eq, PartialEq)]
+pub enum FilterKind {
+    Bassboost,
+    Nightcore,
+    Vaporwave,
+    Speed,
+    Pitch,
+    #[choice(name = "8D", value = "8d")]
+    EightD,
+    #[choice(name = "Off", value = "off")]
+    Off,
+}
+
+const MIN_FACTOR: f32 = 0.5;
+const MAX_FACTOR: f32 = 2.0;
+
+/// Effect that is applied to songs through ffmpeg
+#[derive(Copy, Clone, PartialEq)]
+pub enum AudioFilter {
+    Bassboost,
+    Nightcore,
+    Vaporwave,
+    Speed(f32),
+    Pitch(f32),
+    EightD,
+}
+
+impl AudioFilter {
+    /// Argument for ffmpeg's `-af`
+    pub fn ffmpeg_filter(self) -> String {
+        match self {
+            Self::Bassboost => "bass=g=10".to_owned(),
+            Self::Nightcore => resample(1.25),
+            Self::Vaporwave => resample(0.8),
+            Self::Speed(factor) => format!("atempo={}", factor),
+            // Resampling changes both pitch and speed so the speed is reverted afterwards
+            Self::Pitch(factor) => format!("{},atempo={}", resample(factor), 1.0 / factor),
+            Self::EightD => "apulsator=hz=0.125".to_owned(),
+        }
+    }
+
+    /// How much faster than the original the filtered song plays
+    pub fn speed(self) -> f64 {
+        match self {
+            Self::Nightcore => 1.25,
+            Self::Vaporwave => 0.8,
+            Self::Speed(factor) => factor as f64,
+            Self::Bassboost | Self::Pitch(_) | Self::EightD => 1.0,
+        }
+    }
+
+    fn name(self) -> String {
+        match self {
+            Self::Bassboost => "bassboost".to_owned(),
+            Self::Nightcore => "nightcore".to_owned(),
+            Self::Vaporwave => "vaporwave".to_owned(),
+            Self::Speed(factor) => format!("{}x speed", factor),
+            Self::Pitch(factor) => format!("{}x pitch", factor),
+            Self::EightD => "8D".to_owned(),
+        }
+    }
+}
+
+/// Changes speed and pitch together, independent of the song's sample rate
+fn resample(factor: f32) -> String {
+    format!(
+        "aresample=48000,asetrate={},aresample=48000",
+        (48_000.0 * factor).round()
+    )
+}
+
+pub async fn filter(
+    ctx: Arc<Context>,
+    command: ApplicationCommand,
+    args: FilterArgs,
+) -> BotResult<()> {
+    let FilterArgs { effect, factor } = args;
+    let author_id = command.user_id()?;
+    let guild_id = command.require_guild_id()?;
+
+    let call = match require_call(&ctx, &command).await? {
+        Some(call) => call,
+        None => return Ok(()),
+    };
+
+    let call = call.lock().await;
+    if let Err(content) = ensure_same_channel(&ctx, &call, guild_id, author_id) {
+        let builder = MessageBuilder::new().error(content);
+        return command.create_message(&ctx, builder).await;
+    }
+
+    if !is_dj(&ctx, command.member.as_ref()) {
+        let builder = MessageBuilder::new().error("You need the DJ role to apply filters!");
+        return command.create_message(&ctx, builder).await;
+    }
+
+    let factor = match (effect, factor) {
+        (FilterKind::Speed | FilterKind::Pitch, None) => {
+            let content = "You need to specify a factor for speed and pitch!";
+            let builder = MessageBuilder::new().error(content);
+            return command.create_message(&ctx, builder).await;
+        }
+        (_, Some(factor)) if !(MIN_FACTOR..=MAX_FACTOR).contains(&factor) => {
+            let content = format!(
+                "The factor must be between {} and {}!",
+                MIN_FACTOR, MAX_FACTOR
+            );
+            let builder = MessageBuilder::new().error(content);
+            return command.create_message(&ctx, builder).await;
+        }
+        (_, factor) => factor.unwrap_or(1.0),
+    };
+
+    let filter = match effect {
+        FilterKind::Bassboost => Some(AudioFilter::Bassboost),
+        FilterKind::Nightcore => Some(AudioFilter::Nightcore),
+        FilterKind::Vaporwave => Some(AudioFilter::Vaporwave),
+        FilterKind::Speed => Some(AudioFilter::Speed(factor)),
+        FilterKind::Pitch => Some(AudioFilter::Pitch(factor)),
+        FilterKind::EightD => Some(AudioFilter::EightD),
+        FilterKind::Off => None,
+    };
+
+    info!(
+        "Setting audio filter of guild {} to {}...",
+        guild_id,
+        effect.value()
+    );
+
+    let old_speed = audio_filter(&ctx, guild_id).map_or(1.0, AudioFilter::speed);
+    let new_speed = filter.map_or(1.0, AudioFilter::speed);
+
+    match filter {
+        Some(filter) => {
+            ctx.audio_filters.insert(guild_id, filter);
+        }
+        None => {
+            ctx.audio_filters.remove(&guild_id);
+        }
+    }
+
+    // Restart the current song at the same spot so the filter applies right away
+    if let Some(handle) = call.queue().current().filter(|handle| handle.is_seekable()) {
+        let position = handle.get_info().await?.position;
+        handle.seek_time(position.mul_f64(old_speed / new_speed))?;
+    }
+
+    let content = match filter {
+        Some(filter) => format!("Applied the {} filter!", filter.name()),
+        None => "Removed the audio filter!".to_owned(),
+    };
+
+    let builder = MessageBuilder::new().embed(content);
+    command.create_message(&ctx, builder).await
+}
+
+pub fn audio_filter(ctx: &Context, guild_id: GuildId) -> Option<AudioFilter> {
+    ctx.audio_filters.get(&guild_id).map(|filter| *filter)
+}
@@ -0,0 +1,187 @@
+use std::sync::Arc;
+
+use songbird::tracks::PlayMode;
+use tokio::time::{self, Duration, Instant};
+use twilight_model::id::{ChannelId, GuildId};
+
+use crate::{
+    context::Context,
+    error::BotResult,
+    utils::{spawn_guarded, EmbedBuilder},
+};
+
+use super::{count_listeners, now_playing::refresh_panel, save_queue};
+
+/// Voice connection of a guild that is watched for inactivity
+pub struct IdleTracker {
+    /// Channel to post the notice in once the bot leaves
+    channel_id: ChannelId,
+    /// Since when nothing is playing or nobody is listening
+    idle_since: Option<Instant>,
+}
+
+/// Start watching the guild's voice connection, notices are posted in the given channel
+pub fn track_voice(ctx: &Arc<Context>, guild_id: GuildId, channel_id: ChannelId) {
+    ctx.idle_trackers.insert(
+        guild_id,
+        IdleTracker {
+            channel_id,
+            idle_since: None,
+        },
+    );
+
+    // Songs are usually enqueued right after joining so check after that happened
+    spawn_guarded(ctx, check_idle(Arc::clone(ctx), guild_id));
+}
+
+/// Re-evaluate whether the guild is idle, e.g. because a song ended or someone left
+pub async fn check_idle(ctx: Arc<Context>, guild_id: GuildId) {
+    if !ctx.idle_trackers.contains_key(&guild_id) {
+        return;
+    }
+
+    let idle = match is_idle(&ctx, guild_id).await {
+        Some(idle) => idle,
+        None => {
+            // The bot is no longer in a voice channel
+            ctx.idle_trackers.remove(&guild_id);
+
+            return;
+        }
+    };
+
+    let since = match ctx.idle_trackers.get_mut(&guild_id) {
+        Some(mut tracker) => match (idle, tracker.idle_since) {
+            (true, None) => {
+                let now = Instant::now();
+                tracker.idle_since = Some(now);
+
+                now
+            }
+            (false, _) => {
+                tracker.idle_since = None;
+
+                return;
+            }
+            (true, Some(_)) => return,
+        },
+        None => return,
+    };
+
+    let minutes = ctx.guild_music_settings(guild_id).idle_timeout_mins;
+
+    // Staying forever was configured
+    if minutes == 0 {
+        return;
+    }
+
+    spawn_guarded(
+        &ctx,
+        leave_after(Arc::clone(&ctx), guild_id, since, minutes),
+    );
+}
+
+/// `None` if the bot is not in a voice channel of the guild
+async fn is_idle(ctx: &Context, guild_id: GuildId) -> Option<bool> {
+    let call_lock = ctx.songbird.get(guild_id)?;
+    let call = call_lock.lock().await;
+    let channel_id = ChannelId(call.current_channel()?.0);
+
+    if count_listeners(ctx, channel_id) == 0 {
+        return Some(true);
+    }
+
+    let current = match call.queue().current() {
+        Some(current) => current,
+        None => return Some(true),
+    };
+
+    drop(call);
+
+    // A song that just ended might not have been removed from the queue yet
+    let idle = match current.get_info().await {
+        Ok(state) => matches!(state.playing, PlayMode::End | PlayMode::Stop),
+        Err(_) => true,
+    };
+
+    Some(idle)
+}
+
+async fn leave_after(ctx: Arc<Context>, guild_id: GuildId, since: Instant, minutes: u16) {
+    let timeout = Duration::from_secs(minutes as u64 * 60);
+    time::sleep_until(since + timeout).await;
+
+    // Something happened in the meantime
+    let tracker = match ctx
+        .idle_trackers
+        .remove_if(&guild_id, |_, tracker| tracker.idle_since == Some(since))
+    {
+        Some((_, tracker)) => tracker,
+        None => return,
+    };
+
+    info!(
+        "Leaving voice in guild {} after {} minutes of inactivity",
+        guild_id, minutes
+    );
+
+    let saved = match leave(&ctx, guild_id).await {
+        Ok(saved) => saved,
+        Err(why) => {
+            unwind_error!(warn, why, "Failed to leave idle voice channel: {}");
+
+            false
+        }
+    };
+
+    let mut content = format!(
+        "Left the voice channel after {} minute{} without music or listeners",
+        minutes,
+        if minutes != 1 { "s" } else { "" }
+    );
+
+    if saved {
+        content.push_str("\nThe remaining songs can be brought back with /music queue restore");
+    }
+
+    if let Err(why) = post_notice(&ctx, tracker.channel_id, content).await {
+        unwind_error!(warn, why, "Failed to post idle notice: {}");
+    }
+}
+
+/// Save what is left of the queue and clear everything tied to the voice connection.
+/// Returns whether there were songs left to save.
+async fn leave(ctx: &Context, guild_id: GuildId) -> BotResult<bool> {
+    save_queue(ctx, guild_id).await?;
+
+    let saved = match ctx.songbird.get(guild_id) {
+        Some(call_lock) => {
+            let call = call_lock.lock().await;
+            let saved = !call.queue().is_empty();
+            call.queue().stop();
+
+            saved
+        }
+        None => false,
+    };
+
+    ctx.songbird.remove(guild_id).await?;
+    ctx.audio_filters.remove(&guild_id);
+    ctx.loop_modes.remove(&guild_id);
+    ctx.skip_votes.remove(&guild_id);
+    refresh_panel(ctx, guild_id).await?;
+
+    Ok(saved)
+}
+
+async fn post_notice(ctx: &Context, channel_id: ChannelId, content: String) -> BotResult<()> {
+    let embed = EmbedBuilder::new().description(content).build();
+
+    ctx.http
+        .create_message(channel_id)
+        .embeds(&[embed])?
+        .exec()
+        .await?;
+
+    Ok(())
+}
@@ -0,0 +1,17 @@
+mod play;
+mod rescan;
+mod search;
+
+pub use play::LibraryPlay;
+pub use rescan::LibraryRescan;
+pub use search::LibrarySearch;
+
+#[command]
+#[description = "Search and play songs of the bot's local music library"]
+#[guild_only]
+#[name = "library"]
+#[subcommands = "LibrarySearch, LibraryPlay, LibraryRescan"]
+pub struct Library;
+
+/// Error for bots without `MUSIC_LIBRARY_DIR`
+const NO_LIBRARY: &str = "There is no music library configured for this bot!";
@@ -0,0 +1,92 @@
+use std::sync::Arc;
+
+use twilight_model::application::interaction::ApplicationCommand;
+
+use crate::{
+    commands::music::{enqueue, song_source, track_voice},
+    context::Context,
+    error::BotResult,
+    utils::{ApplicationCommandExt, MessageBuilder},
+};
+
+use super::NO_LIBRARY;
+
+#[command]
+#[args = "LibraryPlayArgs"]
+#[description = "Play the best matching song of the music library"]
+#[in_voice]
+#[name = "play"]
+#[run = "play"]
+pub struct LibraryPlay;
+
+#[derive(CommandArgs)]
+pub struct LibraryPlayArgs {
+    #[option(description = "Specify a title, artist, album or file name")]
+    query: String,
+}
+
+pub async fn play(
+    ctx: Arc<Context>,
+    command: ApplicationCommand,
+    args: LibraryPlayArgs,
+) -> BotResult<()> {
+    let LibraryPlayArgs { query } = args;
+
+    command.start_thinking(&ctx).await?;
+
+    let author_id = command.user_id()?;
+    let guild_id = command.require_guild_id()?;
+
+    if ctx.library.is_none() {
+        let builder = MessageBuilder::new().error(NO_LIBRARY);
+        return command.update_message(&ctx, builder).await;
+    }
+
+    let track = match ctx.database.search_library(&query, 1).await?.pop() {
+        Some(track) => track,
+        None => {
+            let content = format!("No songs of the library match `{}`", query);
+            let builder = MessageBuilder::new().error(content);
+            return command.update_message(&ctx, builder).await;
+        }
+    };
+
+    let channel_id = match ctx
+        .cache
+        .voice_state(author_id, guild_id)
+        .and_then(|state| state.channel_id)
+    {
+        Some(id) => id,
+        None => {
+            let builder = MessageBuilder::new().error("You aren't in a voice channel!");
+            return command.update_message(&ctx, builder).await;
+        }
+    };
+
+    let (call_lock, result) = ctx.songbird.join(guild_id, channel_id).await;
+    result?;
+    track_voice(&ctx, guild_id, command.channel_id);
+
+    let song = match song_source(&ctx, guild_id, track.path.clone()).await {
+        Ok(song) => song,
+        Err(why) => {
+            unwind_error!(warn, why, "Failed to open library file {}: {}", track.path);
+            let content = format!("Failed to play **{}**", track.name());
+            let builder = MessageBuilder::new().error(content);
+            return command.update_message(&ctx, builder).await;
+        }
+    };
+
+    let mut call = call_lock.lock().await;
+    let content = if call.queue().is_empty() {
+        format!("Started playing **{}**", track.name())
+    } else {
+        format!("Added **{}** to the queue", track.name())
+    };
+
+    enqueue(&ctx, &mut call, song, author_id).await?;
+    drop(call);
+
+    let builder = MessageBuilder::new().embed(content);
+    command.update_message(&ctx, builder).await
+}
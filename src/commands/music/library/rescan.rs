@@ -0,0 +1,41 @@
+use std::sync::Arc;
+
+use twilight_model::application::interaction::ApplicationCommand;
+
+use crate::{
+    context::Context,
+    error::BotResult,
+    utils::{ApplicationCommandExt, MessageBuilder},
+};
+
+use super::NO_LIBRARY;
+
+#[command]
+#[description = "Index the music library again to pick up new or changed files"]
+#[name = "rescan"]
+#[owner_only]
+#[run = "rescan"]
+pub struct LibraryRescan;
+
+pub async fn rescan(ctx: Arc<Context>, command: ApplicationCommand) -> BotResult<()> {
+    let library = match ctx.library {
+        Some(ref library) => library,
+        None => {
+            let builder = MessageBuilder::new().error(NO_LIBRARY);
+            return command.create_message(&ctx, builder).await;
+        }
+    };
+
+    command.start_thinking(&ctx).await?;
+
+    info!("Indexing music library...");
+    let count = library.index(&ctx.database).await?;
+
+    let content = format!(
+        "Indexed {} song{} of the music library",
+        count,
+        if count != 1 { "s" } else { "" }
+    );
+    let builder = MessageBuilder::new().embed(content);
+    command.update_message(&ctx, builder).await
+}
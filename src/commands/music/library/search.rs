@@ -0,0 +1,103 @@
+use std::{fmt::Write, sync::Arc};
+
+use twilight_model::{application::interaction::ApplicationCommand, channel::embed::Embed};
+
+use crate::{
+    context::Context,
+    error::BotResult,
+    pagination::{paginate, Pagination},
+    utils::{datetime::sec_to_minsec, ApplicationCommandExt, EmbedBuilder, Footer, MessageBuilder},
+};
+
+use super::NO_LIBRARY;
+
+const ENTRIES_PER_PAGE: usize = 10;
+const MAX_RESULTS: usize = 100;
+
+#[command]
+#[args = "LibrarySearchArgs"]
+#[description = "Search the music library by title, artist, album or file name"]
+#[name = "search"]
+#[run = "search"]
+pub struct LibrarySearch;
+
+#[derive(CommandArgs)]
+pub struct LibrarySearchArgs {
+    #[option(description = "Specify what to search for")]
+    query: String,
+}
+
+pub async fn search(
+    ctx: Arc<Context>,
+    command: ApplicationCommand,
+    args: LibrarySearchArgs,
+) -> BotResult<()> {
+    let LibrarySearchArgs { query } = args;
+
+    if ctx.library.is_none() {
+        let builder = MessageBuilder::new().error(NO_LIBRARY);
+        return command.create_message(&ctx, builder).await;
+    }
+
+    let tracks = ctx.database.search_library(&query, MAX_RESULTS).await?;
+
+    if tracks.is_empty() {
+        let content = format!("No songs of the library match `{}`", query);
+        let builder = MessageBuilder::new().error(content);
+        return command.create_message(&ctx, builder).await;
+    }
+
+    let entries = tracks
+        .iter()
+        .map(|track| {
+            let mut entry = format!("**{}**", track.name());
+
+            if let Some(ref album) = track.album {
+                let _ = write!(entry, " • {}", album);
+            }
+
+            if let Some(duration) = track.duration {
+                let _ = write!(entry, " `{}`", sec_to_minsec(duration.as_secs() as u32));
+            }
+
+            entry
+        })
+        .collect();
+
+    let pagination = LibraryPagination {
+        title: format!("LIBRARY SONGS MATCHING `{}`:", query),
+        entries,
+    };
+
+    paginate(ctx, &command, pagination).await
+}
+
+struct LibraryPagination {
+    title: String,
+    entries: Vec<String>,
+}
+
+impl Pagination for LibraryPagination {
+    fn pages(&self) -> usize {
+        (self.entries.len() + ENTRIES_PER_PAGE - 1) / ENTRIES_PER_PAGE
+    }
+
+    fn render(&self, page: usize) -> Embed {
+        let mut content = String::new();
+        let start = page * ENTRIES_PER_PAGE;
+
+        for (entry, i) in self.entries[start..]
+            .iter()
+            .take(ENTRIES_PER_PAGE)
+            .zip(start + 1..)
+        {
+            let _ = writeln!(content, "`{}.` {}", i, entry);
+        }
+
+        EmbedBuilder::new()
+            .description(content)
+            .title(self.title.as_str())
+            .footer(Footer::new(format!("Page {}/{}", page + 1, self.pages())))
+            .build()
+    }
+}
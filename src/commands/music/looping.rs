@@ -0,0 +1,105 @@
+use std::sync::Arc;
+
+use songbird::Call;
+use twilight_model::{application::interaction::ApplicationCommand, id::GuildId};
+
+use crate::{
+    commands::music::{ensure_same_channel, is_dj, require_call},
+    context::Context,
+    error::BotResult,
+    utils::{ApplicationCommandExt, MessageBuilder},
+};
+
+#[command]
+#[args = "LoopArgs"]
+#[description = "Repeat the current song or the whole queue"]
+#[in_voice]
+#[name = "loop"]
+#[run = "looping"]
+pub struct Loop;
+
+#[derive(CommandArgs)]
+pub struct LoopArgs {
+    #[option(description = "Specify what should be repeated")]
+    mode: LoopMode,
+}
+
+#[derive(Copy, Clone, CommandChoices, Eq, PartialEq)]
+pub enum LoopMode {
+    #[choice(name = "Current song", value = "track")]
+    Track,
+    #[choice(name = "Whole queue", value = "queue")]
+    Queue,
+    #[choice(name = "Off", value = "off")]
+    Off,
+}
+
+pub async fn looping(
+    ctx: Arc<Context>,
+    command: ApplicationCommand,
+    args: LoopArgs,
+) -> BotResult<()> {
+    let LoopArgs { mode } = args;
+    let author_id = command.user_id()?;
+    let guild_id = command.require_guild_id()?;
+
+    let call = match require_call(&ctx, &command).await? {
+        Some(call) => call,
+        None => return Ok(()),
+    };
+
+    let call = call.lock().await;
+    if let Err(content) = ensure_same_channel(&ctx, &call, guild_id, author_id) {
+        let builder = MessageBuilder::new().error(content);
+        return command.create_message(&ctx, builder).await;
+    }
+
+    if !is_dj(&ctx, command.member.as_ref()) {
+        let builder = MessageBuilder::new().error("You need the DJ role to loop songs!");
+        return command.create_message(&ctx, builder).await;
+    }
+
+    info!(
+        "Setting loop mode of guild {} to {}...",
+        guild_id,
+        mode.value()
+    );
+
+    set_loop_mode(&ctx, guild_id, &call, mode);
+
+    let content = match mode {
+        LoopMode::Track => "Looping the current song!",
+        LoopMode::Queue => "Looping the whole queue!",
+        LoopMode::Off => "Stopped looping!",
+    };
+
+    let builder = MessageBuilder::new().embed(content);
+    command.create_message(&ctx, builder).await
+}
+
+pub fn loop_mode(ctx: &Context, guild_id: GuildId) -> LoopMode {
+    ctx.loop_modes
+        .get(&guild_id)
+        .map_or(LoopMode::Off, |mode| *mode)
+}
+
+/// Apply the mode to the current song and remember it for upcoming songs
+pub fn set_loop_mode(ctx: &Context, guild_id: GuildId, call: &Call, mode: LoopMode) {
+    // Songs looped through the queue are re-added by the track's end handler
+    if let Some(handle) = call.queue().current() {
+        let result = match mode {
+            LoopMode::Track => handle.enable_loop(),
+            LoopMode::Queue | LoopMode::Off => handle.disable_loop(),
+        };
+
+        if let Err(why) = result {
+            unwind_error!(warn, why, "Failed to change loop of current song: {}");
+        }
+    }
+
+    if mode == LoopMode::Off {
+        ctx.loop_modes.remove(&guild_id);
+    } else {
+        ctx.loop_modes.insert(guild_id, mode);
+    }
+}
@@ -0,0 +1,210 @@
+use std::{process::Stdio, sync::Arc};
+
+use songbird::{
+    tracks::TrackHandle, typemap::TypeMapKey, Event, EventContext, EventHandler, TrackEvent,
+};
+use tokio::process::Command;
+use twilight_model::id::GuildId;
+
+use crate::{
+    context::Context,
+    error::{BotResult, Error},
+    utils::{matcher, spawn_guarded, ytdl},
+};
+
+/// Loudness that normalized songs are adjusted to, in LUFS
+const TARGET_LUFS: f32 = -16.0;
+/// Only the start of long songs is analysed
+const ANALYSED_SECS: &str = "300";
+/// Keeps quiet songs from being boosted into clipping and loud ones from going silent
+const MIN_GAIN: f32 = 0.1;
+const MAX_GAIN: f32 = 2.0;
+/// Amount of songs whose loudness is remembered
+const CACHE_SIZE: usize = 5000;
+
+/// Start a new song at the guild's default volume and, if normalization is enabled,
+/// adjust it once the song starts and its loudness is known
+pub fn apply_volume(
+    ctx: &Arc<Context>,
+    guild_id: GuildId,
+    handle: &TrackHandle,
+    source: Option<String>,
+) {
+    let settings = ctx.guild_music_settings(guild_id);
+    let volume = settings.volume_percent as f32 / 100.0;
+
+    if let Err(why) = handle.set_volume(volume) {
+        unwind_error!(warn, why, "Failed to set volume of new song: {}");
+    }
+
+    let source = match source {
+        Some(source) if settings.normalize => source,
+        _ => return,
+    };
+
+    // Only songs that actually start are analysed, otherwise playlists
+    // would spawn an analysis for every queued song at once
+    let normalize = Normalize {
+        ctx: Arc::clone(ctx),
+        source,
+    };
+
+    if let Err(why) = handle.add_event(Event::Track(TrackEvent::Play), normalize) {
+        unwind_error!(warn, why, "Failed to add normalize event: {}");
+    }
+}
+
+struct Normalize {
+    ctx: Arc<Context>,
+    source: String,
+}
+
+/// Factor that the volume of a normalized song is scaled by, stored in the track's typemap
+#[derive(Copy, Clone)]
+struct Gain(f32);
+
+impl TypeMapKey for Gain {
+    type Value = Gain;
+}
+
+/// Normalization gain of the song, 1 if it's not normalized (yet)
+pub async fn normalization_gain(handle: &TrackHandle) -> f32 {
+    handle
+        .typemap()
+        .read()
+        .await
+        .get::<Gain>()
+        .map_or(1.0, |gain| gain.0)
+}
+
+#[async_trait]
+impl EventHandler for Normalize {
+    async fn act(&self, ctx: &EventContext<'_>) -> Option<Event> {
+        let handle = match ctx {
+            EventContext::Track(&[(_, handle)]) => handle.clone(),
+            _ => return None,
+        };
+
+        let task_ctx = Arc::clone(&self.ctx);
+        let source = self.source.clone();
+
+        // Analysing can take a while so the song plays at the default volume meanwhile
+        spawn_guarded(&self.ctx, async move {
+            let loudness = match loudness(&task_ctx, &source).await {
+                Ok(Some(loudness)) => loudness,
+                Ok(None) => return,
+                Err(why) => {
+                    unwind_error!(warn, why, "Failed to measure loudness of {}: {}", source);
+                    return;
+                }
+            };
+
+            let gain = 10_f32
+                .powf((TARGET_LUFS - loudness) / 20.0)
+                .clamp(MIN_GAIN, MAX_GAIN);
+
+            // The song might have been skipped already
+            let volume = match handle.get_info().await {
+                Ok(state) => state.volume,
+                Err(_) => return,
+            };
+
+            debug!("Normalizing {} ({} LUFS) by {:.2}", source, loudness, gain);
+
+            // Scaling the current volume keeps one that was set through `/volume` meanwhile
+            let _ = handle.set_volume(volume * gain);
+            handle.typemap().write().await.insert::<Gain>(Gain(gain));
+        });
+
+        // Resuming a paused song fires the event again
+        Some(Event::Cancel)
+    }
+}
+
+/// Integrated loudness of the song in LUFS, measured once per source
+async fn loudness(ctx: &Context, source: &str) -> BotResult<Option<f32>> {
+    if let Some(loudness) = ctx.loudness.get(source) {
+        return Ok(Some(*loudness));
+    }
+
+    // Local files and direct links can be read by ffmpeg, everything else is streamed
+    let input = if matcher::is_audio_file_url(source) || !source.starts_with("http") {
+        source.to_owned()
+    } else {
+        ytdl::stream_url(source).await?
+    };
+
+    let output = Command::new("ffmpeg")
+        .args(&["-hide_banner", "-nostats", "-t", ANALYSED_SECS, "-i"])
+        .arg(&input)
+        .args(&["-af", "ebur128=framelog=quiet", "-f", "null", "-"])
+        .stdin(Stdio::null())
+        .output()
+        .await?;
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    if !output.status.success() {
+        let last_line = stderr.lines().last().unwrap_or_default();
+
+        return Err(Error::Ffmpeg(last_line.trim().to_owned()));
+    }
+
+    let loudness = integrated_loudness(&stderr);
+
+    // TTS clips are only played once so caching them would just evict songs
+    let cached = source.starts_with("http")
+        || ctx
+            .library
+            .as_ref()
+            .map_or(false, |library| library.contains(source));
+
+    if let Some(loudness) = loudness.filter(|_| cached) {
+        // Drop an arbitrary entry instead of letting the cache grow forever
+        if ctx.loudness.len() >= CACHE_SIZE {
+            let evicted = ctx.loudness.iter().next().map(|entry| entry.key().clone());
+
+            if let Some(evicted) = evicted {
+                ctx.loudness.remove(&evicted);
+            }
+        }
+
+        ctx.loudness.insert(source.to_owned(), loudness);
+    }
+
+    Ok(loudness)
+}
+
+/// Parse the `I: -14.2 LUFS` line from the summary of ffmpeg's ebur128 filter
+fn integrated_loudness(output: &str) -> Option<f32> {
+    output
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix("I:"))
+        .last()
+        .and_then(|value| value.trim().strip_suffix("LUFS"))
+        .and_then(|value| value.trim().parse::<f32>().ok())
+        .filter(|loudness| loudness.is_finite())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_integrated_loudness() {
+        let output = "\
+[Parsed_ebur128_0 @ 0x5581] Summary:
+
+  Integrated loudness:
+    I:         -14.5 LUFS
+    Threshold: -24.8 LUFS
+
+  Loudness range:
+    LRA:         5.3 LU
+";
+
+        assert_eq!(integrated_loudness(output), Some(-14.5));
+        assert_eq!(integrated_loudness("no summary"), None);
+        assert_eq!(integrated_loudness("    I:  nan LUFS"), None);
+    }
+}
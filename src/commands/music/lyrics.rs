@@ -0,0 +1,148 @@
+use std::{mem, sync::Arc};
+
+use twilight_model::{
+    application::interaction::ApplicationCommand, channel::embed::Embed, id::GuildId,
+};
+
+use crate::{
+    context::Context,
+    database,
+    error::BotResult,
+    lyrics::LyricsQuery,
+    pagination::{paginate_deferred, Pagination},
+    utils::{ApplicationCommandExt, EmbedBuilder, Footer, MessageBuilder, DESCRIPTION_SIZE},
+};
+
+#[command]
+#[args = "LyricsArgs"]
+#[description = "Show the lyrics of the current song or of a given one"]
+pub struct Lyrics;
+
+#[derive(CommandArgs)]
+pub struct LyricsArgs {
+    #[option(description = "Specify a song like `artist - title`, defaults to the current song")]
+    song: Option<String>,
+}
+
+pub async fn lyrics(
+    ctx: Arc<Context>,
+    command: ApplicationCommand,
+    args: LyricsArgs,
+) -> BotResult<()> {
+    let guild_id = command.require_guild_id()?;
+
+    // Looking up lyrics can take longer than discord waits for a response
+    command.start_thinking(&ctx).await?;
+
+    let query = match args.song {
+        Some(ref song) => LyricsQuery::parse(song),
+        None => match current_song(&ctx, guild_id).await {
+            Some(query) => query,
+            None => {
+                let content = "No song is currently playing, specify one to look up instead!";
+                let builder = MessageBuilder::new().error(content);
+                return command.update_message(&ctx, builder).await;
+            }
+        },
+    };
+
+    let key = query.key();
+
+    let lyrics = match ctx.database.get_lyrics(&key).await? {
+        Some(lyrics) => Some(lyrics),
+        None => {
+            info!("Looking up lyrics for `{}`...", query);
+            let lyrics = ctx.lyrics.lyrics(&query).await?;
+
+            if let Some(ref lyrics) = lyrics {
+                if let Err(why) = ctx.database.insert_lyrics(&key, lyrics).await {
+                    unwind_error!(warn, why, "Failed to cache lyrics: {}");
+                }
+            }
+
+            lyrics
+        }
+    };
+
+    match lyrics {
+        Some(lyrics) => paginate_deferred(ctx, &command, LyricsPagination::new(lyrics)).await,
+        None => {
+            let content = format!("Couldn't find any lyrics for `{}`", query);
+            let builder = MessageBuilder::new().error(content);
+            command.update_message(&ctx, builder).await
+        }
+    }
+}
+
+/// Prefers the artist and track tags over the title which often contains more than the song name
+async fn current_song(ctx: &Context, guild_id: GuildId) -> Option<LyricsQuery> {
+    let call_lock = ctx.songbird.get(guild_id)?;
+    let handle = call_lock.lock().await.queue().current()?;
+    let metadata = handle.metadata();
+
+    match (&metadata.artist, &metadata.track) {
+        (Some(artist), Some(track)) => Some(LyricsQuery {
+            artist: Some(artist.clone()),
+            title: track.clone(),
+        }),
+        _ => metadata.title.as_deref().map(LyricsQuery::parse),
+    }
+}
+
+struct LyricsPagination {
+    title: String,
+    pages: Vec<String>,
+}
+
+impl LyricsPagination {
+    fn new(lyrics: database::Lyrics) -> Self {
+        Self {
+            title: lyrics.title,
+            pages: split_pages(&lyrics.text),
+        }
+    }
+}
+
+impl Pagination for LyricsPagination {
+    fn pages(&self) -> usize {
+        self.pages.len()
+    }
+
+    fn render(&self, page: usize) -> Embed {
+        EmbedBuilder::new()
+            .description(self.pages[page].as_str())
+            .title(self.title.as_str())
+            .footer(Footer::new(format!("Page {}/{}", page + 1, self.pages())))
+            .build()
+    }
+}
+
+/// Split the text at line breaks so each page fits into an embed description
+fn split_pages(text: &str) -> Vec<String> {
+    let mut pages = Vec::new();
+    let mut page = String::new();
+
+    for line in text.lines() {
+        // Lines that don't fit on a page by themselves are cut off
+        let line = match line.char_indices().nth(DESCRIPTION_SIZE) {
+            Some((idx, _)) => &line[..idx],
+            None => line,
+        };
+
+        if !page.is_empty() && page.len() + line.len() + 1 > DESCRIPTION_SIZE {
+            pages.push(mem::take(&mut page));
+        }
+
+        if !page.is_empty() {
+            page.push('\n');
+        }
+
+        page.push_str(line);
+    }
+
+    if !page.is_empty() || pages.is_empty() {
+        pages.push(page);
+    }
+
+    pages
+}
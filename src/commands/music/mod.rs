@@ -1,17 +1,60 @@
-mod clear;
+mod autoplay;
+mod filter;
+mod idle;
+mod library;
+mod looping;
+mod loudness;
+mod lyrics;
+mod now_playing;
 mod pause;
 mod play;
+mod playlist;
+mod previous;
 mod queue;
+mod replay;
+mod search;
+mod seek;
+mod settings;
 mod skip;
+mod source;
+mod stats;
 mod stop;
+mod track;
 mod tts;
 mod volume;
+mod vote;
 
-pub use clear::Clear;
+pub use autoplay::Autoplay;
+pub use filter::{AudioFilter, Filter};
+pub use idle::{check_idle, track_voice, IdleTracker};
+pub use library::Library;
+pub use looping::{Loop, LoopMode};
+pub use loudness::{apply_volume, normalization_gain};
+pub use lyrics::Lyrics;
+pub use now_playing::{handle_now_playing, NowPlaying, NowPlayingPanel, NOW_PLAYING_PREFIX};
 pub use pause::Pause;
-pub use play::Play;
-pub use queue::Queue;
+pub use play::{enqueue_all, Play};
+pub use playlist::Playlists;
+pub use previous::{remember_track, PlayedTrack, Previous};
+pub use queue::{
+    save_queue, Queue, QueueClear, QueueDedupe, QueueMove, QueueRemove, QueueRestore, QueueShow,
+    QueueShuffle,
+};
+pub use replay::Replay;
+pub use search::SEARCH_PICK;
+pub use seek::Seek;
+pub use settings::Settings;
 pub use skip::Skip;
+pub use source::{song_source, ytdl_source};
+pub use stats::Stats;
 pub use stop::Stop;
+pub use track::{count_listeners, enqueue, ensure_same_channel, is_dj, requester, set_requester};
 pub use tts::Tts;
 pub use volume::Volume;
+pub use vote::{handle_vote, start_vote, SkipVote, VOTE_SKIP};
+
+#[command]
+#[description = "Play music and text-to-speech in voice channels"]
+#[guild_only]
+#[subcommands = "Play, NowPlaying, Lyrics, Pause, Skip, Previous, Replay, Seek, Stop, Queue, Volume, Loop, Filter, Tts, Stats, Settings"]
+pub struct Music;
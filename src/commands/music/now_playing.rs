@@ -0,0 +1,411 @@
+use std::{fmt::Write, sync::Arc};
+
+use songbird::tracks::PlayMode;
+use tokio::time::{self, Duration};
+use twilight_model::{
+    application::{
+        component::{button::ButtonStyle, ActionRow, Button, Component},
+        interaction::{ApplicationCommand, MessageComponentInteraction},
+    },
+    channel::embed::Embed,
+    id::{ChannelId, GuildId, MessageId},
+};
+
+use crate::{
+    commands::music::{is_dj, requester},
+    context::Context,
+    error::{BotResult, Error},
+    utils::{
+        datetime::sec_to_minsec, spawn_guarded, ApplicationCommandExt, EmbedBuilder, Footer,
+        MessageBuilder, MessageComponentExt,
+    },
+};
+
+use super::{
+    looping::{loop_mode, set_loop_mode, LoopMode},
+    skip::skip_tracks,
+};
+
+/// Prefix of the custom ID of all now playing buttons
+pub const NOW_PLAYING_PREFIX: &str = "np_";
+
+const PAUSE: &str = "np_pause";
+const SKIP: &str = "np_skip";
+const LOOP: &str = "np_loop";
+const VOLUME_DOWN: &str = "np_vol_down";
+const VOLUME_UP: &str = "np_vol_up";
+
+/// How often the progress of the panel is updated
+const UPDATE_INTERVAL: Duration = Duration::from_secs(10);
+
+const PROGRESS_BAR_LEN: usize = 20;
+const VOLUME_STEP: f32 = 0.25;
+const MAX_VOLUME: f32 = 2.0;
+
+/// Message that displays the current song of a guild
+#[derive(Copy, Clone)]
+pub struct NowPlayingPanel {
+    channel_id: ChannelId,
+    message_id: MessageId,
+}
+
+#[command]
+#[description = "Display the current song with buttons to control it"]
+#[in_voice]
+#[name = "nowplaying"]
+#[run = "now_playing"]
+pub struct NowPlaying;
+
+pub async fn now_playing(ctx: Arc<Context>, command: ApplicationCommand) -> BotResult<()> {
+    let guild_id = command.require_guild_id()?;
+
+    let (embed, components) = match render(&ctx, guild_id).await? {
+        Some(panel) => panel,
+        None => {
+            let builder = MessageBuilder::new().error("No song is currently playing!");
+            return command.create_message(&ctx, builder).await;
+        }
+    };
+
+    let builder = MessageBuilder::from(embed).components(&components);
+    command.create_message(&ctx, builder).await?;
+
+    let message = ctx
+        .http
+        .get_interaction_original(&command.token)?
+        .exec()
+        .await?
+        .model()
+        .await?;
+
+    let panel = NowPlayingPanel {
+        channel_id: message.channel_id,
+        message_id: message.id,
+    };
+
+    // Only the newest panel of a guild is kept up to date
+    if let Some(old) = ctx.now_playing.insert(guild_id, panel) {
+        let content = "Use the newer panel further down instead";
+
+        if let Err(why) = close_panel(&ctx, old, content).await {
+            unwind_error!(warn, why, "Failed to close old now playing panel: {}");
+        }
+    }
+
+    spawn_guarded(
+        &ctx,
+        update_periodically(Arc::clone(&ctx), guild_id, message.id),
+    );
+
+    Ok(())
+}
+
+pub async fn handle_now_playing(
+    ctx: Arc<Context>,
+    component: MessageComponentInteraction,
+) -> BotResult<()> {
+    let user_id = component.user_id()?;
+    let guild_id = component.guild_id.ok_or(Error::MissingGuild)?;
+
+    let current_panel = ctx
+        .now_playing
+        .get(&guild_id)
+        .map_or(false, |panel| panel.message_id == component.message.id);
+
+    if !current_panel {
+        let content = "This panel is outdated, use /music nowplaying again";
+        let builder = MessageBuilder::new().error(content).ephemeral(true);
+        return component.create_message(&ctx, builder).await;
+    }
+
+    let call_lock = match ctx.songbird.get(guild_id) {
+        Some(call) => call,
+        None => {
+            let builder = MessageBuilder::new()
+                .error("I'm not in a voice channel anymore!")
+                .ephemeral(true);
+            return component.create_message(&ctx, builder).await;
+        }
+    };
+
+    let call = call_lock.lock().await;
+    let user_channel = ctx
+        .cache
+        .voice_state(user_id, guild_id)
+        .and_then(|state| state.channel_id);
+
+    match (user_channel, call.current_channel()) {
+        (Some(id1), Some(id2)) if id1.0 == id2.0 => {}
+        _ => {
+            let content = "You need to be in my voice channel to use these buttons!";
+            let builder = MessageBuilder::new().error(content).ephemeral(true);
+            return component.create_message(&ctx, builder).await;
+        }
+    }
+
+    let handle = match call.queue().current() {
+        Some(handle) => handle,
+        None => {
+            let builder = MessageBuilder::new()
+                .error("No song is currently playing!")
+                .ephemeral(true);
+            return component.create_message(&ctx, builder).await;
+        }
+    };
+
+    let dj = is_dj(&ctx, component.member.as_ref());
+
+    match component.data.custom_id.as_str() {
+        PAUSE => {
+            if handle.get_info().await?.playing == PlayMode::Pause {
+                call.queue().resume()?;
+            } else {
+                call.queue().pause()?;
+            }
+        }
+        SKIP => {
+            if !dj && requester(&handle).await != Some(user_id) {
+                let content = "Only DJs and whoever requested the song can skip it here, \
+                    use /music skip to start a vote instead!";
+                let builder = MessageBuilder::new().error(content).ephemeral(true);
+                return component.create_message(&ctx, builder).await;
+            }
+
+            info!("Skipping current song through now playing panel...");
+            skip_tracks(&call, 1)?;
+        }
+        LOOP => {
+            if !dj {
+                let builder = MessageBuilder::new()
+                    .error("You need the DJ role to loop songs!")
+                    .ephemeral(true);
+                return component.create_message(&ctx, builder).await;
+            }
+
+            let mode = match loop_mode(&ctx, guild_id) {
+                LoopMode::Off => LoopMode::Track,
+                LoopMode::Track => LoopMode::Queue,
+                LoopMode::Queue => LoopMode::Off,
+            };
+
+            set_loop_mode(&ctx, guild_id, &call, mode);
+        }
+        VOLUME_DOWN | VOLUME_UP => {
+            let volume = handle.get_info().await?.volume;
+
+            let volume = if component.data.custom_id == VOLUME_UP {
+                (volume + VOLUME_STEP).min(MAX_VOLUME)
+            } else {
+                (volume - VOLUME_STEP).max(0.0)
+            };
+
+            handle.set_volume(volume)?;
+        }
+        custom_id => warn!("Received unknown now playing button `{}`", custom_id),
+    }
+
+    drop(call);
+
+    match render(&ctx, guild_id).await? {
+        Some((embed, components)) => {
+            let builder = MessageBuilder::from(embed).components(&components);
+            component.update_message(&ctx, builder).await
+        }
+        None => {
+            ctx.now_playing.remove(&guild_id);
+
+            let builder = MessageBuilder::new()
+                .embed("Nothing is playing anymore")
+                .components(&[]);
+
+            component.update_message(&ctx, builder).await
+        }
+    }
+}
+
+/// Update the guild's panel right away, e.g. because a new song started
+pub async fn refresh_panel(ctx: &Context, guild_id: GuildId) -> BotResult<()> {
+    let panel = match ctx.now_playing.get(&guild_id) {
+        Some(panel) => *panel,
+        None => return Ok(()),
+    };
+
+    match render(ctx, guild_id).await? {
+        Some((embed, components)) => {
+            ctx.http
+                .update_message(panel.channel_id, panel.message_id)
+                .embeds(&[embed])?
+                .components(Some(&components))?
+                .exec()
+                .await?;
+        }
+        None => {
+            let removed = ctx
+                .now_playing
+                .remove_if(&guild_id, |_, p| p.message_id == panel.message_id);
+
+            if removed.is_some() {
+                close_panel(ctx, panel, "Nothing is playing anymore").await?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+async fn update_periodically(ctx: Arc<Context>, guild_id: GuildId, message_id: MessageId) {
+    let mut interval = time::interval(UPDATE_INTERVAL);
+
+    // The first tick completes immediately
+    interval.tick().await;
+
+    loop {
+        interval.tick().await;
+
+        let current = ctx
+            .now_playing
+            .get(&guild_id)
+            .map_or(false, |panel| panel.message_id == message_id);
+
+        if !current {
+            return;
+        }
+
+        if let Err(why) = refresh_panel(&ctx, guild_id).await {
+            unwind_error!(warn, why, "Failed to update now playing panel: {}");
+        }
+    }
+}
+
+async fn close_panel(ctx: &Context, panel: NowPlayingPanel, content: &str) -> BotResult<()> {
+    let embed = EmbedBuilder::new().description(content).build();
+
+    ctx.http
+        .update_message(panel.channel_id, panel.message_id)
+        .embeds(&[embed])?
+        .components(Some(&[]))?
+        .exec()
+        .await?;
+
+    Ok(())
+}
+
+/// Embed and buttons for the guild's current song, `None` if nothing is playing
+async fn render(ctx: &Context, guild_id: GuildId) -> BotResult<Option<(Embed, Vec<Component>)>> {
+    let (handle, upcoming) = match ctx.songbird.get(guild_id) {
+        Some(call_lock) => {
+            let call = call_lock.lock().await;
+
+            match call.queue().current() {
+                Some(handle) => (handle, call.queue().len() - 1),
+                None => return Ok(None),
+            }
+        }
+        None => return Ok(None),
+    };
+
+    // The song might have ended in the meantime
+    let state = match handle.get_info().await {
+        Ok(state) => state,
+        Err(_) => return Ok(None),
+    };
+
+    let metadata = handle.metadata();
+    let paused = state.playing == PlayMode::Pause;
+    let position = state.position.as_secs() as u32;
+
+    let mut description = String::new();
+
+    let _ = write!(
+        description,
+        "{} `{}` {}",
+        if paused { "⏸" } else { "▶" },
+        sec_to_minsec(position),
+        progress_bar(position, metadata.duration.map(|d| d.as_secs() as u32))
+    );
+
+    if let Some(duration) = metadata.duration {
+        let _ = write!(
+            description,
+            " `{}`",
+            sec_to_minsec(duration.as_secs() as u32)
+        );
+    }
+
+    let _ = write!(
+        description,
+        "\n🔊 {:.0}% • 🔁 {}",
+        state.volume * 100.0,
+        match loop_mode(ctx, guild_id) {
+            LoopMode::Track => "Current song",
+            LoopMode::Queue => "Whole queue",
+            LoopMode::Off => "Off",
+        }
+    );
+
+    if let Some(user_id) = requester(&handle).await {
+        let _ = write!(description, "\nRequested by <@{}>", user_id);
+    }
+
+    let mut builder = EmbedBuilder::new()
+        .title(metadata.title.as_deref().unwrap_or("<UNKNOWN>"))
+        .description(description)
+        .footer(Footer::new(format!(
+            "{} song{} up next",
+            upcoming,
+            if upcoming != 1 { "s" } else { "" }
+        )));
+
+    if let Some(ref url) = metadata.source_url {
+        builder = builder.url(url);
+    }
+
+    if let Some(ref thumbnail) = metadata.thumbnail {
+        builder = builder.thumbnail(thumbnail);
+    }
+
+    Ok(Some((builder.build(), components(paused))))
+}
+
+fn progress_bar(position: u32, duration: Option<u32>) -> String {
+    let filled = match duration {
+        Some(duration) if duration > 0 => {
+            (position.min(duration) as usize * PROGRESS_BAR_LEN) / duration as usize
+        }
+        _ => 0,
+    }
+    .min(PROGRESS_BAR_LEN - 1);
+
+    let mut bar = String::with_capacity(PROGRESS_BAR_LEN * 3);
+
+    for i in 0..PROGRESS_BAR_LEN {
+        bar.push(if i == filled { '🔘' } else { '▬' });
+    }
+
+    bar
+}
+
+fn components(paused: bool) -> Vec<Component> {
+    let buttons = vec![
+        button(PAUSE, if paused { "▶" } else { "⏸" }),
+        button(SKIP, "⏭"),
+        button(LOOP, "🔁"),
+        button(VOLUME_DOWN, "🔉"),
+        button(VOLUME_UP, "🔊"),
+    ];
+
+    vec![Component::ActionRow(ActionRow {
+        components: buttons,
+    })]
+}
+
+fn button(custom_id: &str, label: &str) -> Component {
+    Component::Button(Button {
+        custom_id: Some(custom_id.to_owned()),
+        disabled: false,
+        emoji: None,
+        label: Some(label.to_owned()),
+        style: ButtonStyle::Secondary,
+        url: None,
+    })
+}
@@ -1,66 +1,47 @@
 use std::sync::Arc;
 
-use songbird::{
-    input::{Input, Restartable},
-    Event, EventContext, EventHandler, TrackEvent,
-};
+use songbird::input::Input;
 use twilight_model::{
-    application::{
-        command::{ChoiceCommandOptionData, CommandOption},
-        interaction::{
-            application_command::{CommandData, CommandDataOption},
-            ApplicationCommand,
-        },
-    },
-    gateway::presence::{ActivityType, Status},
+    application::interaction::ApplicationCommand,
+    id::{GuildId, UserId},
 };
 
 use crate::{
     context::Context,
     error::BotResult,
-    utils::{matcher, ApplicationCommandExt, EmbedBuilder, MessageBuilder},
+    utils::{
+        matcher, spawn_guarded,
+        ytdl::{self, PlaylistEntry},
+        ApplicationCommandExt, EmbedBuilder, MessageBuilder,
+    },
 };
 
+use super::{enqueue, search::pick_search_result, song_source, track_voice, ytdl_source};
+
 #[command]
 #[args = "PlayArgs"]
 #[description = "Play a given song"]
-#[options = "play_options"]
+#[in_voice]
 pub struct Play;
 
+#[derive(CommandArgs)]
 pub struct PlayArgs {
+    #[option(
+        description = "Specify a song name, youtube url, youtube playlist url, or link to an audio file"
+    )]
     song: String,
-}
-
-impl PlayArgs {
-    async fn parse_options(_: Arc<Context>, data: CommandData) -> BotResult<Self> {
-        for option in data.options {
-            if let CommandDataOption::String { name, value } = option {
-                if name == "song" {
-                    return Ok(Self { song: value });
-                }
-            }
-        }
-
-        unreachable!()
-    }
-}
-
-fn play_options() -> Vec<CommandOption> {
-    let option_data = ChoiceCommandOptionData {
-        choices: vec![],
-        description: "Specify a song name or youtube url".to_string(),
-        name: "song".to_string(),
-        required: true,
-    };
-
-    vec![CommandOption::String(option_data)]
+    #[option(
+        description = "Specify whether to play the first search result instead of picking one",
+        default = "false"
+    )]
+    first: bool,
 }
 
 pub async fn play(ctx: Arc<Context>, command: ApplicationCommand, args: PlayArgs) -> BotResult<()> {
     command.start_thinking(&ctx).await?;
 
     let author_id = command.user_id()?;
-    let guild_id = command.guild_id.expect("Missing Guild ID for play command");
+    let guild_id = command.require_guild_id()?;
 
     let channel_id = match ctx
         .cache
@@ -83,6 +64,8 @@ pub async fn play(ctx: Arc<Context>, command: ApplicationCommand, args: PlayArgs
     //     return Err(success.into());
     // }
 
+    track_voice(&ctx, guild_id, command.channel_id);
+
     info!(
         "Joined channel {} after play command by {}",
         if let Some(channel) = ctx.cache.guild_channel(channel_id) {
@@ -93,19 +76,32 @@ pub async fn play(ctx: Arc<Context>, command: ApplicationCommand, args: PlayArgs
         command.username()?
     );
 
-    let PlayArgs { song } = args;
-    let id = matcher::get_youtube_id(&song);
+    let PlayArgs { song, first } = args;
+
+    if matcher::get_youtube_playlist_id(&song).is_some() {
+        return play_playlist(ctx, command, song, author_id, guild_id).await;
+    }
+
     let yt_search = song;
-    // let yt_search = if let Some(_) = id {
-    //     song
-    // } else {
-    //     format!("ytsearch1:{}", song)
-    // };
-
-    match Restartable::ytdl_search(&yt_search, false).await {
-        Ok(song) => {
+
+    let direct =
+        matcher::get_youtube_id(&yt_search).is_some() || matcher::is_audio_file_url(&yt_search);
+
+    let result = if direct {
+        song_source(&ctx, guild_id, yt_search.clone()).await
+    } else if first {
+        let search = format!("ytsearch1:{}", yt_search);
+        ytdl_source(&ctx, guild_id, search).await.map(Input::from)
+    } else {
+        match pick_search_result(&ctx, &command, &yt_search).await? {
+            Some(url) => ytdl_source(&ctx, guild_id, url).await.map(Input::from),
+            None => return Ok(()),
+        }
+    };
+
+    match result {
+        Ok(input) => {
             // info!("In ytdl search");
-            let input = Input::from(song);
 
             if let Some(call_lock) = ctx.songbird.get(guild_id) {
                 // info!("Got call lock");
@@ -140,12 +136,7 @@ pub async fn play(ctx: Arc<Context>, command: ApplicationCommand, args: PlayArgs
                     builder = builder.image(thumbnail);
                 }
 
-                call.enqueue_source(input);
-                call.queue().modify_queue(|q| {
-                    q.back().map(|q| {
-                        q.add_event(Event::Track(TrackEvent::Play), TrackStart(Arc::clone(&ctx)))
-                    })
-                });
+                enqueue(&ctx, &mut call, input, author_id).await?;
                 command.update_message(&ctx, builder).await?;
 
                 // ctx.trackdata.write().replace(handle);
@@ -165,30 +156,124 @@ pub async fn play(ctx: Arc<Context>, command: ApplicationCommand, args: PlayArgs
     Ok(())
 }
 
-struct TrackStart(Arc<Context>);
+async fn play_playlist(
+    ctx: Arc<Context>,
+    command: ApplicationCommand,
+    url: String,
+    author_id: UserId,
+    guild_id: GuildId,
+) -> BotResult<()> {
+    let limit = ctx.guild_music_settings(guild_id).playlist_limit as usize;
 
-#[async_trait]
-impl EventHandler for TrackStart {
-    async fn act(&self, ctx: &songbird::EventContext<'_>) -> Option<songbird::Event> {
-        if let EventContext::Track(&[(_, track)]) = ctx {
-            info!("Changing activity");
+    let entries = match ytdl::playlist_entries(&url, limit).await {
+        Ok(entries) if !entries.is_empty() => entries,
+        Ok(_) => {
+            let builder = MessageBuilder::new().error("The playlist is empty!");
+            return command.update_message(&ctx, builder).await;
+        }
+        Err(why) => {
+            unwind_error!(warn, why, "Failed to list playlist {}: {}", url);
+            let builder = MessageBuilder::new().error("Failed to load the playlist!");
+            return command.update_message(&ctx, builder).await;
+        }
+    };
+
+    let source = if entries.len() == limit {
+        format!("the playlist (limited to {} songs)", limit)
+    } else {
+        "the playlist".to_owned()
+    };
+
+    let urls = entries.iter().map(PlaylistEntry::url).collect();
+
+    enqueue_all(ctx, command, urls, author_id, source).await
+}
+
+/// Respond right away and add the songs in the background since fetching them takes a while.
+/// The response is updated with the amount of added songs once all of them are queued.
+pub async fn enqueue_all(
+    ctx: Arc<Context>,
+    command: ApplicationCommand,
+    urls: Vec<String>,
+    requester: UserId,
+    source: String,
+) -> BotResult<()> {
+    let guild_id = command.require_guild_id()?;
+
+    let content = format!(
+        "Adding {} song{} from {}...",
+        urls.len(),
+        if urls.len() != 1 { "s" } else { "" },
+        source
+    );
+
+    info!("{}", content);
+
+    let builder = MessageBuilder::new().embed(content);
+    command.update_message(&ctx, builder).await?;
+
+    let task_ctx = Arc::clone(&ctx);
+
+    spawn_guarded(&ctx, async move {
+        let total = urls.len();
+        let added = enqueue_urls(&task_ctx, guild_id, requester, urls).await;
 
-            let metadata = track.metadata();
+        let content = format!(
+            "Added {}/{} song{} from {}",
+            added,
+            total,
+            if total != 1 { "s" } else { "" },
+            source
+        );
 
-            let metadata_str = match (&metadata.track, &metadata.artist, &metadata.title) {
-                (Some(track), Some(artist), _) => format!("🎵 {} - {} 🎵", artist, track),
-                (.., Some(title)) => format!("🎵 {} 🎵", title),
-                _ => "🎵 UNKNOWN 🎵".to_string(),
-            };
-            let result = self
-                .0
-                .set_shard_activity(0, Status::Online, ActivityType::Playing, metadata_str)
-                .await;
+        // The interaction token expires after 15 minutes
+        let builder = MessageBuilder::new().embed(content);
 
-            if let Err(e) = result {
-                unwind_error!(warn, e, "Failed to set song activity: {}")
+        if let Err(why) = command.update_message(&task_ctx, builder).await {
+            unwind_error!(warn, why, "Failed to report added songs: {}");
+        }
+    });
+
+    Ok(())
+}
+
+/// Add the songs one by one and return how many of them were added
+async fn enqueue_urls(
+    ctx: &Arc<Context>,
+    guild_id: GuildId,
+    requester: UserId,
+    urls: Vec<String>,
+) -> usize {
+    let mut added = 0;
+
+    for url in urls {
+        // Lazy songs only fetch their metadata until they are played
+        let song = match song_source(ctx, guild_id, url.clone()).await {
+            Ok(song) => song,
+            Err(why) => {
+                unwind_error!(warn, why, "Failed to add song {}: {}", url);
+
+                continue;
             }
+        };
+
+        let call_lock = match ctx.songbird.get(guild_id) {
+            Some(call) => call,
+            None => break,
+        };
+
+        let mut call = call_lock.lock().await;
+
+        // Stop once the bot left the channel
+        if call.current_channel().is_none() {
+            break;
+        }
+
+        match enqueue(ctx, &mut call, song, requester).await {
+            Ok(_) => added += 1,
+            Err(why) => unwind_error!(warn, why, "Failed to enqueue song: {}"),
         }
-        None
     }
+
+    added
 }
@@ -0,0 +1,99 @@
+use std::sync::Arc;
+
+use songbird::input::{Input, Restartable};
+use twilight_model::application::interaction::ApplicationCommand;
+
+use crate::{
+    context::Context,
+    database::PlaylistTrack,
+    error::BotResult,
+    utils::{matcher, ApplicationCommandExt, MessageBuilder},
+};
+
+use super::{can_edit, describe, owner};
+
+#[command]
+#[args = "PlaylistAddArgs"]
+#[description = "Add a song to a playlist"]
+#[name = "add"]
+#[run = "add"]
+pub struct PlaylistAdd;
+
+#[derive(CommandArgs)]
+pub struct PlaylistAddArgs {
+    #[option(description = "Specify the name of the playlist")]
+    name: String,
+    #[option(description = "Specify a song name or youtube url")]
+    song: String,
+    #[option(
+        description = "Specify whether the playlist belongs to the server instead of you",
+        default = "false"
+    )]
+    server: bool,
+}
+
+pub async fn add(
+    ctx: Arc<Context>,
+    command: ApplicationCommand,
+    args: PlaylistAddArgs,
+) -> BotResult<()> {
+    let PlaylistAddArgs { name, song, server } = args;
+    let owner = owner(&command, server)?;
+
+    if !can_edit(&ctx, &command, owner) {
+        let content = "You need the DJ role to edit server playlists!";
+        let builder = MessageBuilder::new().error(content);
+        return command.create_message(&ctx, builder).await;
+    }
+
+    command.start_thinking(&ctx).await?;
+
+    let playlist = match ctx.database.get_playlist(owner, &name).await? {
+        Some(playlist) => playlist,
+        None => {
+            let content = format!("There is no {}!", describe(owner, &name));
+            let builder = MessageBuilder::new().error(content);
+            return command.update_message(&ctx, builder).await;
+        }
+    };
+
+    // Lazy songs only fetch their metadata
+    let result = if matcher::get_youtube_id(&song).is_some() {
+        Restartable::ytdl(song.clone(), true).await
+    } else {
+        Restartable::ytdl_search(&song, true).await
+    };
+
+    let metadata = match result {
+        Ok(song) => Input::from(song).metadata,
+        Err(why) => {
+            unwind_error!(warn, why, "Failed to find song {} for playlist: {}", song);
+            let builder = MessageBuilder::new().error("Didn't find any results");
+            return command.update_message(&ctx, builder).await;
+        }
+    };
+
+    let track = match metadata.source_url {
+        Some(url) => PlaylistTrack {
+            url,
+            title: metadata.title,
+            duration: metadata.duration,
+        },
+        None => {
+            let builder = MessageBuilder::new().error("The song can't be saved, it has no url");
+            return command.update_message(&ctx, builder).await;
+        }
+    };
+
+    info!("Adding {} to playlist {}...", track.url, playlist.name);
+    ctx.database.add_playlist_track(playlist.id, &track).await?;
+
+    let content = format!(
+        "Added **{}** to {}!",
+        track.title.as_deref().unwrap_or("<UNKNOWN>"),
+        describe(owner, &playlist.name)
+    );
+
+    let builder = MessageBuilder::new().embed(content);
+    command.update_message(&ctx, builder).await
+}
@@ -0,0 +1,117 @@
+use std::sync::Arc;
+
+use twilight_model::application::interaction::ApplicationCommand;
+
+use crate::{
+    context::Context,
+    database::PlaylistTrack,
+    error::BotResult,
+    utils::{ApplicationCommandExt, MessageBuilder},
+};
+
+use super::{can_edit, describe, owner, MAX_NAME_LEN};
+
+#[command]
+#[args = "PlaylistCreateArgs"]
+#[description = "Create a new playlist"]
+#[name = "create"]
+#[run = "create"]
+pub struct PlaylistCreate;
+
+#[derive(CommandArgs)]
+pub struct PlaylistCreateArgs {
+    #[option(description = "Specify the name of the playlist")]
+    name: String,
+    #[option(
+        description = "Specify whether the songs of the current queue should be added",
+        default = "false"
+    )]
+    from_queue: bool,
+    #[option(
+        description = "Specify whether the playlist belongs to the server instead of you",
+        default = "false"
+    )]
+    server: bool,
+}
+
+pub async fn create(
+    ctx: Arc<Context>,
+    command: ApplicationCommand,
+    args: PlaylistCreateArgs,
+) -> BotResult<()> {
+    let PlaylistCreateArgs {
+        name,
+        from_queue,
+        server,
+    } = args;
+
+    let name = name.trim();
+    let owner = owner(&command, server)?;
+
+    if name.is_empty() || name.chars().count() > MAX_NAME_LEN {
+        let content = format!(
+            "The name must be between 1 and {} characters long!",
+            MAX_NAME_LEN
+        );
+        let builder = MessageBuilder::new().error(content);
+        return command.create_message(&ctx, builder).await;
+    }
+
+    if !can_edit(&ctx, &command, owner) {
+        let content = "You need the DJ role to create server playlists!";
+        let builder = MessageBuilder::new().error(content);
+        return command.create_message(&ctx, builder).await;
+    }
+
+    let mut tracks = Vec::new();
+
+    if from_queue {
+        let guild_id = command.require_guild_id()?;
+
+        if let Some(call) = ctx.songbird.get(guild_id) {
+            let handles = call.lock().await.queue().current_queue();
+
+            // Only songs with a source can be played again, e.g. no tts
+            for handle in handles {
+                let metadata = handle.metadata();
+
+                if let Some(ref url) = metadata.source_url {
+                    tracks.push(PlaylistTrack {
+                        url: url.to_owned(),
+                        title: metadata.title.clone(),
+                        duration: metadata.duration,
+                    });
+                }
+            }
+        }
+
+        if tracks.is_empty() {
+            let content = "There are no songs in the queue that could be saved!";
+            let builder = MessageBuilder::new().error(content);
+            return command.create_message(&ctx, builder).await;
+        }
+    }
+
+    info!("Creating playlist {} with {} songs...", name, tracks.len());
+
+    if !ctx.database.create_playlist(owner, name, &tracks).await? {
+        let content = format!("There already is {}!", describe(owner, name));
+        let builder = MessageBuilder::new().error(content);
+        return command.create_message(&ctx, builder).await;
+    }
+
+    let mut content = format!("Created {}", describe(owner, name));
+
+    if !tracks.is_empty() {
+        content.push_str(&format!(
+            " with {} song{}",
+            tracks.len(),
+            if tracks.len() != 1 { "s" } else { "" }
+        ));
+    }
+
+    content.push('!');
+
+    let builder = MessageBuilder::new().embed(content);
+    command.create_message(&ctx, builder).await
+}
@@ -0,0 +1,60 @@
+use std::sync::Arc;
+
+use twilight_model::application::interaction::ApplicationCommand;
+
+use crate::{
+    context::Context,
+    error::BotResult,
+    utils::{ApplicationCommandExt, MessageBuilder},
+};
+
+use super::{can_edit, describe, owner};
+
+#[command]
+#[args = "PlaylistDeleteArgs"]
+#[description = "Delete a playlist"]
+#[name = "delete"]
+#[run = "delete"]
+pub struct PlaylistDelete;
+
+#[derive(CommandArgs)]
+pub struct PlaylistDeleteArgs {
+    #[option(description = "Specify the name of the playlist")]
+    name: String,
+    #[option(
+        description = "Specify whether the playlist belongs to the server instead of you",
+        default = "false"
+    )]
+    server: bool,
+}
+
+pub async fn delete(
+    ctx: Arc<Context>,
+    command: ApplicationCommand,
+    args: PlaylistDeleteArgs,
+) -> BotResult<()> {
+    let PlaylistDeleteArgs { name, server } = args;
+    let owner = owner(&command, server)?;
+
+    if !can_edit(&ctx, &command, owner) {
+        let content = "You need the DJ role to delete server playlists!";
+        let builder = MessageBuilder::new().error(content);
+        return command.create_message(&ctx, builder).await;
+    }
+
+    let playlist = match ctx.database.get_playlist(owner, &name).await? {
+        Some(playlist) => playlist,
+        None => {
+            let content = format!("There is no {}!", describe(owner, &name));
+            let builder = MessageBuilder::new().error(content);
+            return command.create_message(&ctx, builder).await;
+        }
+    };
+
+    info!("Deleting playlist {}...", playlist.name);
+    ctx.database.delete_playlist(playlist.id).await?;
+
+    let content = format!("Deleted {}!", describe(owner, &playlist.name));
+    let builder = MessageBuilder::new().embed(content);
+    command.create_message(&ctx, builder).await
+}
@@ -0,0 +1,54 @@
+mod add;
+mod create;
+mod delete;
+mod play;
+mod remove;
+mod show;
+
+pub use add::PlaylistAdd;
+pub use create::PlaylistCreate;
+pub use delete::PlaylistDelete;
+pub use play::PlaylistPlay;
+pub use remove::PlaylistRemove;
+pub use show::PlaylistShow;
+
+use twilight_model::application::interaction::ApplicationCommand;
+
+use crate::{
+    commands::music::is_dj, context::Context, database::PlaylistOwner, error::BotResult,
+    utils::ApplicationCommandExt,
+};
+
+/// Names are limited by the database column
+const MAX_NAME_LEN: usize = 100;
+
+#[command]
+#[description = "Save songs as personal or server playlists"]
+#[guild_only]
+#[name = "playlist"]
+#[subcommands = "PlaylistShow, PlaylistPlay, PlaylistCreate, PlaylistAdd, PlaylistRemove, PlaylistDelete"]
+pub struct Playlists;
+
+/// Owner of the playlist that the command refers to
+fn owner(command: &ApplicationCommand, server: bool) -> BotResult<PlaylistOwner> {
+    if server {
+        command.require_guild_id().map(PlaylistOwner::Guild)
+    } else {
+        command.user_id().map(PlaylistOwner::User)
+    }
+}
+
+/// Everyone manages their own playlists, server playlists are managed by DJs
+fn can_edit(ctx: &Context, command: &ApplicationCommand, owner: PlaylistOwner) -> bool {
+    match owner {
+        PlaylistOwner::User(_) => true,
+        PlaylistOwner::Guild(_) => is_dj(ctx, command.member.as_ref()),
+    }
+}
+
+fn describe(owner: PlaylistOwner, name: &str) -> String {
+    match owner {
+        PlaylistOwner::User(_) => format!("your playlist **{}**", name),
+        PlaylistOwner::Guild(_) => format!("the server playlist **{}**", name),
+    }
+}
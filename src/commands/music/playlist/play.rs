@@ -0,0 +1,95 @@
+use std::{fmt::Write, sync::Arc};
+
+use twilight_model::application::interaction::ApplicationCommand;
+
+use crate::{
+    commands::music::{enqueue_all, track_voice},
+    context::Context,
+    error::BotResult,
+    utils::{ApplicationCommandExt, MessageBuilder},
+};
+
+use super::{describe, owner};
+
+#[command]
+#[args = "PlaylistPlayArgs"]
+#[description = "Add all songs of a playlist to the queue"]
+#[in_voice]
+#[name = "play"]
+#[run = "play"]
+pub struct PlaylistPlay;
+
+#[derive(CommandArgs)]
+pub struct PlaylistPlayArgs {
+    #[option(description = "Specify the name of the playlist")]
+    name: String,
+    #[option(
+        description = "Specify whether the playlist belongs to the server instead of you",
+        default = "false"
+    )]
+    server: bool,
+}
+
+pub async fn play(
+    ctx: Arc<Context>,
+    command: ApplicationCommand,
+    args: PlaylistPlayArgs,
+) -> BotResult<()> {
+    let PlaylistPlayArgs { name, server } = args;
+
+    command.start_thinking(&ctx).await?;
+
+    let author_id = command.user_id()?;
+    let guild_id = command.require_guild_id()?;
+    let owner = owner(&command, server)?;
+
+    let playlist = match ctx.database.get_playlist(owner, &name).await? {
+        Some(playlist) => playlist,
+        None => {
+            let content = format!("There is no {}!", describe(owner, &name));
+            let builder = MessageBuilder::new().error(content);
+            return command.update_message(&ctx, builder).await;
+        }
+    };
+
+    let tracks = ctx.database.get_playlist_tracks(playlist.id).await?;
+
+    if tracks.is_empty() {
+        let content = format!("There are no songs in {}!", describe(owner, &playlist.name));
+        let builder = MessageBuilder::new().error(content);
+        return command.update_message(&ctx, builder).await;
+    }
+
+    let channel_id = match ctx
+        .cache
+        .voice_state(author_id, guild_id)
+        .and_then(|state| state.channel_id)
+    {
+        Some(id) => id,
+        None => {
+            let builder = MessageBuilder::new().error("You aren't in a voice channel!");
+            return command.update_message(&ctx, builder).await;
+        }
+    };
+
+    let (_handle, result) = ctx.songbird.join(guild_id, channel_id).await;
+    result?;
+    track_voice(&ctx, guild_id, command.channel_id);
+
+    let limit = ctx.guild_music_settings(guild_id).playlist_limit as usize;
+    let total = tracks.len();
+
+    let urls = tracks
+        .into_iter()
+        .take(limit)
+        .map(|track| track.url)
+        .collect();
+
+    let mut source = describe(owner, &playlist.name);
+
+    if total > limit {
+        let _ = write!(source, " (limited to {} songs)", limit);
+    }
+
+    enqueue_all(ctx, command, urls, author_id, source).await
+}
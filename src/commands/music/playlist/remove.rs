@@ -0,0 +1,88 @@
+use std::sync::Arc;
+
+use twilight_model::application::interaction::ApplicationCommand;
+
+use crate::{
+    context::Context,
+    error::BotResult,
+    utils::{ApplicationCommandExt, MessageBuilder},
+};
+
+use super::{can_edit, describe, owner};
+
+#[command]
+#[args = "PlaylistRemoveArgs"]
+#[description = "Remove a song from a playlist"]
+#[name = "remove"]
+#[run = "remove"]
+pub struct PlaylistRemove;
+
+#[derive(CommandArgs)]
+pub struct PlaylistRemoveArgs {
+    #[option(description = "Specify the name of the playlist")]
+    name: String,
+    #[option(
+        description = "Specify the position of the song as shown by /playlist show",
+        min = 1
+    )]
+    position: usize,
+    #[option(
+        description = "Specify whether the playlist belongs to the server instead of you",
+        default = "false"
+    )]
+    server: bool,
+}
+
+pub async fn remove(
+    ctx: Arc<Context>,
+    command: ApplicationCommand,
+    args: PlaylistRemoveArgs,
+) -> BotResult<()> {
+    let PlaylistRemoveArgs {
+        name,
+        position,
+        server,
+    } = args;
+
+    let owner = owner(&command, server)?;
+
+    if !can_edit(&ctx, &command, owner) {
+        let content = "You need the DJ role to edit server playlists!";
+        let builder = MessageBuilder::new().error(content);
+        return command.create_message(&ctx, builder).await;
+    }
+
+    let playlist = match ctx.database.get_playlist(owner, &name).await? {
+        Some(playlist) => playlist,
+        None => {
+            let content = format!("There is no {}!", describe(owner, &name));
+            let builder = MessageBuilder::new().error(content);
+            return command.create_message(&ctx, builder).await;
+        }
+    };
+
+    let removed = position >= 1
+        && ctx
+            .database
+            .remove_playlist_track(playlist.id, position - 1)
+            .await?;
+
+    if !removed {
+        let content = format!(
+            "The playlist only has {} song{}!",
+            playlist.len,
+            if playlist.len != 1 { "s" } else { "" }
+        );
+        let builder = MessageBuilder::new().error(content);
+        return command.create_message(&ctx, builder).await;
+    }
+
+    let content = format!(
+        "Removed song {} from {}!",
+        position,
+        describe(owner, &playlist.name)
+    );
+
+    let builder = MessageBuilder::new().embed(content);
+    command.create_message(&ctx, builder).await
+}
@@ -0,0 +1,157 @@
+use std::{fmt::Write, sync::Arc};
+
+use twilight_model::{application::interaction::ApplicationCommand, channel::embed::Embed};
+
+use crate::{
+    context::Context,
+    database::PlaylistOwner,
+    error::BotResult,
+    pagination::{paginate, Pagination},
+    utils::{datetime::sec_to_minsec, ApplicationCommandExt, EmbedBuilder, Footer, MessageBuilder},
+};
+
+use super::{describe, owner};
+
+const ENTRIES_PER_PAGE: usize = 10;
+
+#[command]
+#[args = "PlaylistShowArgs"]
+#[description = "List all playlists or the songs of one"]
+#[name = "show"]
+#[run = "show"]
+pub struct PlaylistShow;
+
+#[derive(CommandArgs)]
+pub struct PlaylistShowArgs {
+    #[option(description = "Specify the name of a playlist to show its songs")]
+    name: Option<String>,
+    #[option(
+        description = "Specify whether the playlist belongs to the server instead of you",
+        default = "false"
+    )]
+    server: bool,
+}
+
+pub async fn show(
+    ctx: Arc<Context>,
+    command: ApplicationCommand,
+    args: PlaylistShowArgs,
+) -> BotResult<()> {
+    let PlaylistShowArgs { name, server } = args;
+
+    let name = match name {
+        Some(name) => name,
+        None => return list_playlists(ctx, command).await,
+    };
+
+    let owner = owner(&command, server)?;
+
+    let playlist = match ctx.database.get_playlist(owner, &name).await? {
+        Some(playlist) => playlist,
+        None => {
+            let content = format!("There is no {}!", describe(owner, &name));
+            let builder = MessageBuilder::new().error(content);
+            return command.create_message(&ctx, builder).await;
+        }
+    };
+
+    let tracks = ctx.database.get_playlist_tracks(playlist.id).await?;
+
+    if tracks.is_empty() {
+        let content = format!(
+            "There are no songs in {} yet!\nAdd some by using /playlist add",
+            describe(owner, &playlist.name)
+        );
+        let builder = MessageBuilder::new().embed(content);
+        return command.create_message(&ctx, builder).await;
+    }
+
+    let entries = tracks
+        .iter()
+        .map(|track| {
+            let mut entry = match track.title {
+                Some(ref title) => format!("[{}]({})", title, track.url),
+                None => track.url.clone(),
+            };
+
+            if let Some(duration) = track.duration {
+                let _ = write!(entry, " `{}`", sec_to_minsec(duration.as_secs() as u32));
+            }
+
+            entry
+        })
+        .collect();
+
+    let pagination = ListPagination {
+        title: format!("PLAYLIST {}:", playlist.name.to_uppercase()),
+        entries,
+    };
+
+    paginate(ctx, &command, pagination).await
+}
+
+async fn list_playlists(ctx: Arc<Context>, command: ApplicationCommand) -> BotResult<()> {
+    let playlists = ctx
+        .database
+        .get_playlists(command.user_id()?, command.guild_id)
+        .await?;
+
+    if playlists.is_empty() {
+        let content = "There are no playlists yet!\nCreate one by using /playlist create";
+        let builder = MessageBuilder::new().embed(content);
+        return command.create_message(&ctx, builder).await;
+    }
+
+    let entries = playlists
+        .iter()
+        .map(|playlist| {
+            format!(
+                "**{}**{} • {} song{}",
+                playlist.name,
+                match playlist.owner {
+                    PlaylistOwner::User(_) => "",
+                    PlaylistOwner::Guild(_) => " (server)",
+                },
+                playlist.len,
+                if playlist.len != 1 { "s" } else { "" }
+            )
+        })
+        .collect();
+
+    let pagination = ListPagination {
+        title: "PLAYLISTS:".to_owned(),
+        entries,
+    };
+
+    paginate(ctx, &command, pagination).await
+}
+
+struct ListPagination {
+    title: String,
+    entries: Vec<String>,
+}
+
+impl Pagination for ListPagination {
+    fn pages(&self) -> usize {
+        (self.entries.len() + ENTRIES_PER_PAGE - 1) / ENTRIES_PER_PAGE
+    }
+
+    fn render(&self, page: usize) -> Embed {
+        let mut content = String::new();
+        let start = page * ENTRIES_PER_PAGE;
+
+        for (entry, i) in self.entries[start..]
+            .iter()
+            .take(ENTRIES_PER_PAGE)
+            .zip(start + 1..)
+        {
+            let _ = writeln!(content, "`{}.` {}", i, entry);
+        }
+
+        EmbedBuilder::new()
+            .description(content)
+            .title(self.title.as_str())
+            .footer(Footer::new(format!("Page {}/{}", page + 1, self.pages())))
+            .build()
+    }
+}
@@ -0,0 +1,191 @@
+use std::{collections::VecDeque, sync::Arc};
+
+use songbird::{tracks::TrackHandle, Call};
+use twilight_model::{
+    application::interaction::ApplicationCommand,
+    id::{GuildId, UserId},
+};
+
+use crate::{
+    commands::music::{enqueue, ensure_same_channel, is_dj, requester},
+    context::Context,
+    error::BotResult,
+    utils::{ApplicationCommandExt, MessageBuilder},
+};
+
+use super::{skip::skip_tracks, source::song_source};
+
+/// How many started songs are remembered per guild
+const HISTORY_SIZE: usize = 25;
+
+/// Song that started playing at some point
+pub struct PlayedTrack {
+    url: String,
+    requester: UserId,
+}
+
+#[command]
+#[description = "Go back to the song that played before the current one"]
+#[in_voice]
+pub struct Previous;
+
+pub async fn previous(ctx: Arc<Context>, command: ApplicationCommand) -> BotResult<()> {
+    command.start_thinking(&ctx).await?;
+
+    let author_id = command.user_id()?;
+    let guild_id = command.require_guild_id()?;
+
+    let call_lock = match ctx.songbird.get(guild_id) {
+        Some(call) => call,
+        None => {
+            let builder = MessageBuilder::new().error("I'm not in a voice channel!");
+            return command.update_message(&ctx, builder).await;
+        }
+    };
+
+    let call = call_lock.lock().await;
+    if let Err(content) = ensure_same_channel(&ctx, &call, guild_id, author_id) {
+        let builder = MessageBuilder::new().error(content);
+        return command.update_message(&ctx, builder).await;
+    }
+
+    let current = call.queue().current();
+    drop(call);
+
+    if let Some(ref current) = current {
+        if !is_dj(&ctx, command.member.as_ref()) && requester(current).await != Some(author_id) {
+            let content = "You can only go back during your own songs unless you have the DJ role!";
+            let builder = MessageBuilder::new().error(content);
+            return command.update_message(&ctx, builder).await;
+        }
+    }
+
+    let current_url = current
+        .as_ref()
+        .and_then(|handle| handle.metadata().source_url.clone());
+
+    let previous = match ctx.played_tracks.get_mut(&guild_id) {
+        Some(mut history) => {
+            // The current song is the newest entry
+            if current_url.is_some()
+                && history.back().map(|track| &track.url) == current_url.as_ref()
+            {
+                history.pop_back();
+            }
+
+            history.pop_back()
+        }
+        None => None,
+    };
+
+    let previous = match previous {
+        Some(previous) => previous,
+        None => {
+            let builder = MessageBuilder::new().error("There is no previous song to go back to!");
+            return command.update_message(&ctx, builder).await;
+        }
+    };
+
+    let song = match song_source(&ctx, guild_id, previous.url.clone()).await {
+        Ok(song) => song,
+        Err(why) => {
+            unwind_error!(
+                warn,
+                why,
+                "Failed to load previous song {}: {}",
+                previous.url
+            );
+
+            let builder = MessageBuilder::new().error("Failed to load the previous song!");
+            return command.update_message(&ctx, builder).await;
+        }
+    };
+
+    // The current song continues after the previous one, this time from the start
+    let replay = match (&current, current_url) {
+        (Some(current), Some(url)) => match song_source(&ctx, guild_id, url).await {
+            Ok(song) => Some((song, requester(current).await.unwrap_or(author_id))),
+            Err(why) => {
+                unwind_error!(warn, why, "Failed to re-add current song: {}");
+
+                None
+            }
+        },
+        _ => None,
+    };
+
+    let mut call = call_lock.lock().await;
+    let handle = enqueue(&ctx, &mut call, song, previous.requester).await?;
+    let title = handle
+        .metadata()
+        .title
+        .clone()
+        .unwrap_or_else(|| "<UNKNOWN>".to_owned());
+
+    // Only interrupt the current song if it's still playing
+    let current = current.filter(|current| {
+        call.queue()
+            .current()
+            .map_or(false, |handle| handle.uuid() == current.uuid())
+    });
+
+    if current.is_some() {
+        move_to(&call, &handle, 1);
+
+        if let Some((song, requester)) = replay {
+            let handle = enqueue(&ctx, &mut call, song, requester).await?;
+            move_to(&call, &handle, 2);
+        }
+
+        info!("Going back to the previous song in guild {}...", guild_id);
+        skip_tracks(&call, 1)?;
+    }
+
+    drop(call);
+
+    let content = format!("Went back to **{}**!", title);
+    let builder = MessageBuilder::new().embed(content);
+    command.update_message(&ctx, builder).await
+}
+
+/// Move the song from the end of the queue to the given position
+fn move_to(call: &Call, handle: &TrackHandle, position: usize) {
+    let uuid = handle.uuid();
+
+    call.queue().modify_queue(|q| {
+        if let Some(idx) = q.iter().rposition(|item| item.uuid() == uuid) {
+            if let Some(item) = q.remove(idx) {
+                q.insert(position.min(q.len()), item);
+            }
+        }
+    });
+}
+
+/// Remember that the song started playing so /music previous can go back to it
+pub async fn remember_track(ctx: &Context, guild_id: GuildId, handle: &TrackHandle) {
+    let url = match handle.metadata().source_url {
+        Some(ref url) => url.clone(),
+        None => return,
+    };
+
+    let requester = match requester(handle).await {
+        Some(requester) => requester,
+        None => return,
+    };
+
+    let mut history = ctx
+        .played_tracks
+        .entry(guild_id)
+        .or_insert_with(VecDeque::new);
+
+    // Songs that play again right away, e.g. replayed ones, are only remembered once
+    if history.back().map_or(false, |track| track.url == url) {
+        return;
+    }
+
+    if history.len() >= HISTORY_SIZE {
+        history.pop_front();
+    }
+
+    history.push_back(PlayedTrack { url, requester });
+}
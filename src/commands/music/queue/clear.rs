@@ -0,0 +1,84 @@
+use std::sync::Arc;
+
+use hashbrown::HashSet;
+use songbird::tracks::TrackError;
+use twilight_model::application::interaction::ApplicationCommand;
+
+use crate::{
+    commands::music::{ensure_same_channel, is_dj, requester, require_call},
+    context::Context,
+    error::BotResult,
+    utils::{ApplicationCommandExt, MessageBuilder},
+};
+
+#[command]
+#[description = "Clear the song queue"]
+#[in_voice]
+#[name = "clear"]
+#[run = "clear"]
+pub struct QueueClear;
+
+pub async fn clear(ctx: Arc<Context>, command: ApplicationCommand) -> BotResult<()> {
+    let author_id = command.user_id()?;
+    let guild_id = command.require_guild_id()?;
+
+    let call = match require_call(&ctx, &command).await? {
+        Some(call) => call,
+        None => return Ok(()),
+    };
+
+    let call = call.lock().await;
+    if let Err(content) = ensure_same_channel(&ctx, &call, guild_id, author_id) {
+        let builder = MessageBuilder::new().error(content);
+        return command.create_message(&ctx, builder).await;
+    }
+
+    if call.queue().is_empty() {
+        let builder = MessageBuilder::new().error("No song is currently playing!");
+        return command.create_message(&ctx, builder).await;
+    }
+
+    // Without being a DJ, only the author's own songs are cleared
+    let dj = is_dj(&ctx, command.member.as_ref());
+    let mut cleared = HashSet::new();
+
+    for handle in call.queue().current_queue().iter().skip(1) {
+        if dj || requester(handle).await == Some(author_id) {
+            cleared.insert(handle.uuid());
+        }
+    }
+
+    info!("Clearing {} songs of the song queue...", cleared.len());
+    let result = call.queue().modify_queue(|q| {
+        for item in q.iter() {
+            if cleared.contains(&item.uuid()) {
+                item.stop()?;
+            }
+        }
+
+        q.retain(|item| !cleared.contains(&item.uuid()));
+
+        Ok::<_, TrackError>(())
+    });
+
+    match result {
+        Ok(_) => {
+            let content = if dj {
+                "Cleared song queue!"
+            } else {
+                "Cleared your songs from the queue!"
+            };
+
+            let builder = MessageBuilder::new().embed(content);
+            let _ = command.create_message(&ctx, builder).await;
+        }
+        Err(e) => {
+            let builder =
+                MessageBuilder::new().error("Failed to clear the whole queue! Blame Joshi :c");
+            let _ = command.create_message(&ctx, builder).await;
+            return Err(e.into());
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,81 @@
+use std::sync::Arc;
+
+use hashbrown::HashSet;
+use songbird::tracks::TrackError;
+use twilight_model::application::interaction::ApplicationCommand;
+
+use crate::{
+    commands::music::{ensure_same_channel, is_dj, require_call},
+    context::Context,
+    error::BotResult,
+    utils::{ApplicationCommandExt, MessageBuilder},
+};
+
+#[command]
+#[description = "Remove songs that are in the queue multiple times"]
+#[in_voice]
+#[name = "dedupe"]
+#[run = "dedupe"]
+pub struct QueueDedupe;
+
+pub async fn dedupe(ctx: Arc<Context>, command: ApplicationCommand) -> BotResult<()> {
+    let author_id = command.user_id()?;
+    let guild_id = command.require_guild_id()?;
+
+    let call = match require_call(&ctx, &command).await? {
+        Some(call) => call,
+        None => return Ok(()),
+    };
+
+    let call = call.lock().await;
+    if let Err(content) = ensure_same_channel(&ctx, &call, guild_id, author_id) {
+        let builder = MessageBuilder::new().error(content);
+        return command.create_message(&ctx, builder).await;
+    }
+
+    if !is_dj(&ctx, command.member.as_ref()) {
+        let content = "You need the DJ role to remove duplicate songs!";
+        let builder = MessageBuilder::new().error(content);
+        return command.create_message(&ctx, builder).await;
+    }
+
+    info!("Removing duplicate songs from the song queue...");
+
+    // Keep the first occurrence of every song, songs without a source are always kept
+    let result = call.queue().modify_queue(|q| {
+        let mut seen = HashSet::new();
+        let mut duplicates = HashSet::new();
+
+        for item in q.iter() {
+            if let Some(ref url) = item.metadata().source_url {
+                if !seen.insert(url.to_owned()) {
+                    item.stop()?;
+                    duplicates.insert(item.uuid());
+                }
+            }
+        }
+
+        q.retain(|item| !duplicates.contains(&item.uuid()));
+
+        Ok::<_, TrackError>(duplicates.len())
+    });
+
+    let removed = match result {
+        Ok(removed) => removed,
+        Err(e) => {
+            let builder =
+                MessageBuilder::new().error("Failed to remove duplicates! Blame Joshi :c");
+            let _ = command.create_message(&ctx, builder).await;
+            return Err(e.into());
+        }
+    };
+
+    let content = match removed {
+        0 => "There are no duplicate songs in the queue!".to_owned(),
+        1 => "Removed 1 duplicate song!".to_owned(),
+        n => format!("Removed {} duplicate songs!", n),
+    };
+
+    let builder = MessageBuilder::new().embed(content);
+    command.create_message(&ctx, builder).await
+}
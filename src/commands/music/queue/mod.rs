@@ -0,0 +1,20 @@
+mod clear;
+mod dedupe;
+mod move_song;
+mod remove;
+mod restore;
+mod show;
+mod shuffle;
+
+pub use clear::QueueClear;
+pub use dedupe::QueueDedupe;
+pub use move_song::QueueMove;
+pub use remove::QueueRemove;
+pub use restore::{save_queue, QueueRestore};
+pub use show::QueueShow;
+pub use shuffle::QueueShuffle;
+
+#[command]
+#[description = "Display or manage the song queue"]
+#[subcommands = "QueueShow, QueueRemove, QueueMove, QueueShuffle, QueueDedupe, QueueClear, QueueRestore"]
+pub struct Queue;
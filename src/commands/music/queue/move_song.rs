@@ -0,0 +1,101 @@
+use std::sync::Arc;
+
+use twilight_model::application::interaction::ApplicationCommand;
+
+use crate::{
+    commands::music::{ensure_same_channel, is_dj, requester, require_call},
+    context::Context,
+    error::BotResult,
+    utils::{ApplicationCommandExt, MessageBuilder},
+};
+
+#[command]
+#[args = "QueueMoveArgs"]
+#[description = "Move a song to a different position in the queue"]
+#[in_voice]
+#[name = "move"]
+#[run = "move_song"]
+pub struct QueueMove;
+
+#[derive(CommandArgs)]
+pub struct QueueMoveArgs {
+    #[option(description = "Specify the current position of the song", min = 1)]
+    from: usize,
+    #[option(description = "Specify the position to move the song to", min = 1)]
+    to: usize,
+}
+
+pub async fn move_song(
+    ctx: Arc<Context>,
+    command: ApplicationCommand,
+    args: QueueMoveArgs,
+) -> BotResult<()> {
+    let QueueMoveArgs { from, to } = args;
+    let author_id = command.user_id()?;
+    let guild_id = command.require_guild_id()?;
+
+    let call = match require_call(&ctx, &command).await? {
+        Some(call) => call,
+        None => return Ok(()),
+    };
+
+    let call = call.lock().await;
+    if let Err(content) = ensure_same_channel(&ctx, &call, guild_id, author_id) {
+        let builder = MessageBuilder::new().error(content);
+        return command.create_message(&ctx, builder).await;
+    }
+
+    let handles = call.queue().current_queue();
+
+    if from.max(to) >= handles.len() {
+        let content = format!(
+            "The queue only goes up to position {}!",
+            handles.len().saturating_sub(1)
+        );
+        let builder = MessageBuilder::new().error(content);
+        return command.create_message(&ctx, builder).await;
+    }
+
+    let handle = &handles[from];
+
+    if !is_dj(&ctx, command.member.as_ref()) && requester(handle).await != Some(author_id) {
+        let content = "You can only move your own songs unless you have the DJ role!";
+        let builder = MessageBuilder::new().error(content);
+        return command.create_message(&ctx, builder).await;
+    }
+
+    info!("Moving song from position {} to {}...", from, to);
+    let uuid = handle.uuid();
+
+    // The queue might have advanced in the meantime so find the song again
+    let moved = call.queue().modify_queue(|q| {
+        let idx = match q.iter().position(|item| item.uuid() == uuid) {
+            Some(idx) if idx > 0 => idx,
+            _ => return false,
+        };
+
+        match q.remove(idx) {
+            Some(item) => {
+                q.insert(to.min(q.len()).max(1), item);
+
+                true
+            }
+            None => false,
+        }
+    });
+
+    if !moved {
+        let builder = MessageBuilder::new().error("The song is no longer in the queue!");
+        return command.create_message(&ctx, builder).await;
+    }
+
+    let title = handle
+        .metadata()
+        .title
+        .clone()
+        .unwrap_or_else(|| "<UNKNOWN>".to_owned());
+
+    let content = format!("Moved **{}** to position {}!", title, to);
+    let builder = MessageBuilder::new().embed(content);
+    command.create_message(&ctx, builder).await
+}
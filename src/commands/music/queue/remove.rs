@@ -0,0 +1,128 @@
+use std::{ops::RangeInclusive, sync::Arc};
+
+use hashbrown::HashSet;
+use songbird::tracks::TrackError;
+use twilight_model::application::interaction::ApplicationCommand;
+
+use crate::{
+    commands::music::{ensure_same_channel, is_dj, requester, require_call},
+    context::Context,
+    error::BotResult,
+    utils::{ApplicationCommandExt, MessageBuilder},
+};
+
+#[command]
+#[args = "QueueRemoveArgs"]
+#[description = "Remove songs from the queue"]
+#[in_voice]
+#[name = "remove"]
+#[run = "remove"]
+pub struct QueueRemove;
+
+#[derive(CommandArgs)]
+pub struct QueueRemoveArgs {
+    #[option(description = "Specify a position of the queue or a range like `3-5`")]
+    position: String,
+}
+
+pub async fn remove(
+    ctx: Arc<Context>,
+    command: ApplicationCommand,
+    args: QueueRemoveArgs,
+) -> BotResult<()> {
+    let author_id = command.user_id()?;
+    let guild_id = command.require_guild_id()?;
+
+    let range = match parse_positions(&args.position) {
+        Some(range) => range,
+        None => {
+            let content = "The position must be a number like `3` or a range like `3-5`!";
+            let builder = MessageBuilder::new().error(content);
+            return command.create_message(&ctx, builder).await;
+        }
+    };
+
+    if *range.start() == 0 {
+        let content = "The current song can't be removed, use /music skip instead!";
+        let builder = MessageBuilder::new().error(content);
+        return command.create_message(&ctx, builder).await;
+    }
+
+    let call = match require_call(&ctx, &command).await? {
+        Some(call) => call,
+        None => return Ok(()),
+    };
+
+    let call = call.lock().await;
+    if let Err(content) = ensure_same_channel(&ctx, &call, guild_id, author_id) {
+        let builder = MessageBuilder::new().error(content);
+        return command.create_message(&ctx, builder).await;
+    }
+
+    let handles = call.queue().current_queue();
+
+    if *range.end() >= handles.len() {
+        let content = format!(
+            "The queue only goes up to position {}!",
+            handles.len().saturating_sub(1)
+        );
+        let builder = MessageBuilder::new().error(content);
+        return command.create_message(&ctx, builder).await;
+    }
+
+    // Without being a DJ, only the author's own songs can be removed
+    let dj = is_dj(&ctx, command.member.as_ref());
+    let mut removed = HashSet::new();
+
+    for handle in &handles[range.clone()] {
+        if !dj && requester(handle).await != Some(author_id) {
+            let content = "You can only remove your own songs unless you have the DJ role!";
+            let builder = MessageBuilder::new().error(content);
+            return command.create_message(&ctx, builder).await;
+        }
+
+        removed.insert(handle.uuid());
+    }
+
+    info!("Removing {} song(s) from the song queue...", removed.len());
+    let result = call.queue().modify_queue(|q| {
+        for item in q.iter() {
+            if removed.contains(&item.uuid()) {
+                item.stop()?;
+            }
+        }
+
+        q.retain(|item| !removed.contains(&item.uuid()));
+
+        Ok::<_, TrackError>(())
+    });
+
+    if let Err(e) = result {
+        let builder = MessageBuilder::new().error("Failed to remove the songs! Blame Joshi :c");
+        let _ = command.create_message(&ctx, builder).await;
+        return Err(e.into());
+    }
+
+    let content = format!(
+        "Removed {} song{} from the queue!",
+        removed.len(),
+        if removed.len() != 1 { "s" } else { "" }
+    );
+    let builder = MessageBuilder::new().embed(content);
+    command.create_message(&ctx, builder).await
+}
+
+/// Parse either a single position or an inclusive range of positions
+/// as displayed by `/music queue show`
+fn parse_positions(input: &str) -> Option<RangeInclusive<usize>> {
+    let (start, end) = match input.split_once('-') {
+        Some((start, end)) => (start.trim().parse().ok()?, end.trim().parse().ok()?),
+        None => {
+            let position = input.trim().parse().ok()?;
+
+            (position, position)
+        }
+    };
+
+    (start <= end).then(|| start..=end)
+}
@@ -0,0 +1,132 @@
+use std::{sync::Arc, time::Duration};
+
+use twilight_model::{application::interaction::ApplicationCommand, id::GuildId};
+
+use crate::{
+    commands::music::{enqueue, requester, song_source, track_voice},
+    context::Context,
+    database::QueuedTrack,
+    error::BotResult,
+    utils::{ApplicationCommandExt, MessageBuilder},
+};
+
+#[command]
+#[description = "Restore the song queue from before the bot restarted"]
+#[in_voice]
+#[name = "restore"]
+#[run = "restore"]
+pub struct QueueRestore;
+
+pub async fn restore(ctx: Arc<Context>, command: ApplicationCommand) -> BotResult<()> {
+    command.start_thinking(&ctx).await?;
+
+    let author_id = command.user_id()?;
+    let guild_id = command.require_guild_id()?;
+    let tracks = ctx.database.get_music_queue(guild_id).await?;
+
+    if tracks.is_empty() {
+        let builder = MessageBuilder::new().error("There is no saved queue for this server!");
+        return command.update_message(&ctx, builder).await;
+    }
+
+    let channel_id = match ctx
+        .cache
+        .voice_state(author_id, guild_id)
+        .and_then(|state| state.channel_id)
+    {
+        Some(id) => id,
+        None => {
+            let builder = MessageBuilder::new().error("You aren't in a voice channel!");
+            return command.update_message(&ctx, builder).await;
+        }
+    };
+
+    let (call_lock, result) = ctx.songbird.join(guild_id, channel_id).await;
+    result?;
+    track_voice(&ctx, guild_id, command.channel_id);
+
+    info!("Restoring {} songs in guild {}...", tracks.len(), guild_id);
+
+    let mut songs = Vec::with_capacity(tracks.len());
+
+    // Resolving songs takes a while so the call isn't locked meanwhile
+    for (track, i) in tracks.into_iter().zip(0..) {
+        match song_source(&ctx, guild_id, track.url.clone()).await {
+            Ok(song) => songs.push((song, track, i)),
+            Err(why) => unwind_error!(warn, why, "Failed to restore song {}: {}", track.url),
+        }
+    }
+
+    let mut call = call_lock.lock().await;
+    let empty = call.queue().is_empty();
+    let restored = songs.len();
+
+    for (song, track, i) in songs {
+        let requester = track.requester.unwrap_or(author_id);
+        let handle = enqueue(&ctx, &mut call, song, requester).await?;
+
+        // Continue the previously playing song where it stopped
+        if i == 0 && empty && track.offset > Duration::ZERO {
+            let _ = handle.seek_time(track.offset);
+        }
+    }
+
+    drop(call);
+    ctx.database.remove_music_queue(guild_id).await?;
+
+    let content = format!(
+        "Restored {} song{}",
+        restored,
+        if restored == 1 { "" } else { "s" }
+    );
+
+    let builder = MessageBuilder::new().embed(content);
+    command.update_message(&ctx, builder).await
+}
+
+/// Save the guild's queue to the database so it can be restored after a restart
+pub async fn save_queue(ctx: &Context, guild_id: GuildId) -> BotResult<()> {
+    let call_lock = match ctx.songbird.get(guild_id) {
+        Some(call) => call,
+        None => return Ok(()),
+    };
+
+    let handles = call_lock.lock().await.queue().current_queue();
+
+    // Don't overwrite a previously saved queue that has not been restored yet
+    if handles.is_empty() {
+        return Ok(());
+    }
+
+    let mut tracks = Vec::with_capacity(handles.len());
+
+    for (handle, i) in handles.iter().zip(0..) {
+        let metadata = handle.metadata();
+
+        // Only songs with a source can be restored, e.g. no tts
+        let url = match metadata.source_url {
+            Some(ref url) => url.to_owned(),
+            None => continue,
+        };
+
+        let offset = if i == 0 {
+            match handle.get_info().await {
+                Ok(state) => state.position,
+                Err(_) => Duration::ZERO,
+            }
+        } else {
+            Duration::ZERO
+        };
+
+        tracks.push(QueuedTrack {
+            url,
+            title: metadata.title.clone(),
+            requester: requester(handle).await,
+            offset,
+        });
+    }
+
+    info!("Saving {} songs of guild {}...", tracks.len(), guild_id);
+
+    ctx.database.save_music_queue(guild_id, &tracks).await
+}
@@ -0,0 +1,168 @@
+use std::{fmt::Write, sync::Arc};
+
+use twilight_model::{application::interaction::ApplicationCommand, channel::embed::Embed};
+
+use crate::{
+    commands::music::{requester, require_call},
+    context::Context,
+    error::BotResult,
+    pagination::{paginate, Pagination},
+    utils::{
+        datetime::sec_to_minsec, ApplicationCommandExt, EmbedBuilder, Footer, MessageBuilder,
+        NUMBER_EMOTES,
+    },
+};
+
+const ENTRIES_PER_PAGE: usize = 10;
+
+#[command]
+#[description = "Display the current song queue"]
+#[guild_only]
+#[name = "show"]
+#[run = "show"]
+pub struct QueueShow;
+
+pub async fn show(ctx: Arc<Context>, command: ApplicationCommand) -> BotResult<()> {
+    let call = match require_call(&ctx, &command).await? {
+        Some(call) => call,
+        None => return Ok(()),
+    };
+
+    let handles = {
+        let call = call.lock().await;
+
+        info!("Displaying current song queue...");
+
+        if call.queue().len() < 2 {
+            let builder = MessageBuilder::new()
+                .embed("The queue is currently empty!\nAdd more songs by using /music play");
+            return command.create_message(&ctx, builder).await;
+        }
+
+        call.queue().current_queue()
+    };
+
+    let mut entries = Vec::with_capacity(handles.len());
+
+    for handle in handles.iter() {
+        let metadata = handle.metadata();
+
+        let mut entry = match (&metadata.title, &metadata.source_url) {
+            (Some(title), Some(url)) => format!("[{}]({})", title, url),
+            (Some(title), None) => title.to_owned(),
+            _ => "<UNKNOWN>".to_owned(),
+        };
+
+        if let Some(duration) = metadata.duration {
+            let _ = write!(entry, " `{}`", sec_to_minsec(duration.as_secs() as u32));
+        }
+
+        if let Some(user_id) = requester(handle).await {
+            let _ = write!(entry, " • <@{}>", user_id);
+        }
+
+        entries.push(entry);
+    }
+
+    paginate(ctx, &command, QueuePagination { entries }).await
+}
+
+struct QueuePagination {
+    entries: Vec<String>,
+}
+
+impl Pagination for QueuePagination {
+    fn pages(&self) -> usize {
+        (self.entries.len() + ENTRIES_PER_PAGE - 1) / ENTRIES_PER_PAGE
+    }
+
+    fn render(&self, page: usize) -> Embed {
+        let mut content = String::new();
+        let start = page * ENTRIES_PER_PAGE;
+
+        for (title, i) in self.entries[start..]
+            .iter()
+            .take(ENTRIES_PER_PAGE)
+            .zip(start..)
+        {
+            let _ = writeln!(
+                content,
+                "{:>3}: {}",
+                DigitIter::new(i as u32).to_emotes(),
+                title
+            );
+        }
+
+        EmbedBuilder::new()
+            .description(content)
+            .title("CURRENT QUEUE:")
+            .footer(Footer::new(format!("Page {}/{}", page + 1, self.pages())))
+            .build()
+    }
+}
+
+struct DigitIter {
+    num: u32,
+    trailing_zeros: u32,
+}
+
+impl DigitIter {
+    fn new(mut n: u32) -> Self {
+        let mut rev = 0;
+        let mut trailing_zeros = 0;
+
+        if n == 0 {
+            return Self {
+                num: 0,
+                trailing_zeros: 1,
+            };
+        }
+
+        while n % 10 == 0 {
+            trailing_zeros += 1;
+            n /= 10;
+        }
+
+        while n > 0 {
+            rev *= 10;
+            rev += n % 10;
+            n /= 10;
+        }
+
+        Self {
+            num: rev,
+            trailing_zeros,
+        }
+    }
+
+    fn to_emotes(self) -> String {
+        if self.num == 0 {
+            return ":musical_note:".to_owned();
+        }
+        let mut result = String::new();
+        for digit in self {
+            result += NUMBER_EMOTES[digit as usize];
+        }
+
+        result
+    }
+}
+
+impl Iterator for DigitIter {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.num > 0 {
+            let digit = self.num % 10;
+            self.num /= 10;
+
+            Some(digit)
+        } else if self.trailing_zeros > 0 {
+            self.trailing_zeros -= 1;
+
+            Some(0)
+        } else {
+            None
+        }
+    }
+}
@@ -0,0 +1,56 @@
+use std::sync::Arc;
+
+use rand::seq::SliceRandom;
+use twilight_model::application::interaction::ApplicationCommand;
+
+use crate::{
+    commands::music::{ensure_same_channel, is_dj, require_call},
+    context::Context,
+    error::BotResult,
+    utils::{ApplicationCommandExt, MessageBuilder},
+};
+
+#[command]
+#[description = "Shuffle the upcoming songs of the queue"]
+#[in_voice]
+#[name = "shuffle"]
+#[run = "shuffle"]
+pub struct QueueShuffle;
+
+pub async fn shuffle(ctx: Arc<Context>, command: ApplicationCommand) -> BotResult<()> {
+    let author_id = command.user_id()?;
+    let guild_id = command.require_guild_id()?;
+
+    let call = match require_call(&ctx, &command).await? {
+        Some(call) => call,
+        None => return Ok(()),
+    };
+
+    let call = call.lock().await;
+    if let Err(content) = ensure_same_channel(&ctx, &call, guild_id, author_id) {
+        let builder = MessageBuilder::new().error(content);
+        return command.create_message(&ctx, builder).await;
+    }
+
+    if !is_dj(&ctx, command.member.as_ref()) {
+        let builder = MessageBuilder::new().error("You need the DJ role to shuffle the queue!");
+        return command.create_message(&ctx, builder).await;
+    }
+
+    if call.queue().len() < 3 {
+        let builder = MessageBuilder::new().error("There is nothing to shuffle!");
+        return command.create_message(&ctx, builder).await;
+    }
+
+    info!("Shuffling song queue...");
+
+    // The current song keeps playing at the front
+    call.queue().modify_queue(|q| {
+        if let Some((_, upcoming)) = q.make_contiguous().split_first_mut() {
+            upcoming.shuffle(&mut rand::thread_rng());
+        }
+    });
+
+    let builder = MessageBuilder::new().embed("Shuffled the queue!");
+    command.create_message(&ctx, builder).await
+}
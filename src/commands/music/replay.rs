@@ -0,0 +1,63 @@
+use std::{sync::Arc, time::Duration};
+
+use twilight_model::application::interaction::ApplicationCommand;
+
+use crate::{
+    commands::music::{ensure_same_channel, is_dj, requester, require_call},
+    context::Context,
+    error::BotResult,
+    utils::{ApplicationCommandExt, MessageBuilder},
+};
+
+#[command]
+#[description = "Play the current song again from the start"]
+#[in_voice]
+pub struct Replay;
+
+pub async fn replay(ctx: Arc<Context>, command: ApplicationCommand) -> BotResult<()> {
+    let author_id = command.user_id()?;
+    let guild_id = command.require_guild_id()?;
+
+    let call = match require_call(&ctx, &command).await? {
+        Some(call) => call,
+        None => return Ok(()),
+    };
+
+    let call = call.lock().await;
+    if let Err(content) = ensure_same_channel(&ctx, &call, guild_id, author_id) {
+        let builder = MessageBuilder::new().error(content);
+        return command.create_message(&ctx, builder).await;
+    }
+
+    let handle = match call.queue().current() {
+        Some(handle) => handle,
+        None => {
+            let builder = MessageBuilder::new().error("No song is currently playing!");
+            return command.create_message(&ctx, builder).await;
+        }
+    };
+
+    if !is_dj(&ctx, command.member.as_ref()) && requester(&handle).await != Some(author_id) {
+        let content = "You can only replay your own songs unless you have the DJ role!";
+        let builder = MessageBuilder::new().error(content);
+        return command.create_message(&ctx, builder).await;
+    }
+
+    if !handle.is_seekable() {
+        let builder = MessageBuilder::new().error("This song can't be restarted!");
+        return command.create_message(&ctx, builder).await;
+    }
+
+    info!("Restarting current song of guild {}...", guild_id);
+    handle.seek_time(Duration::from_secs(0))?;
+
+    let title = handle
+        .metadata()
+        .title
+        .clone()
+        .unwrap_or_else(|| "<UNKNOWN>".to_owned());
+
+    let content = format!("Playing **{}** from the start again!", title);
+    let builder = MessageBuilder::new().embed(content);
+    command.create_message(&ctx, builder).await
+}
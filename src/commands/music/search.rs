@@ -0,0 +1,222 @@
+use std::fmt::Write;
+
+use futures::StreamExt;
+use tokio::time::{self, Duration, Instant};
+use twilight_model::{
+    application::{
+        component::{
+            select_menu::{SelectMenu, SelectMenuOption},
+            ActionRow, Component,
+        },
+        interaction::{ApplicationCommand, Interaction},
+    },
+    gateway::event::Event,
+};
+
+use crate::{
+    context::Context,
+    error::BotResult,
+    utils::{
+        datetime::sec_to_minsec,
+        ytdl::{self, PlaylistEntry},
+        ApplicationCommandExt, EmbedBuilder, MessageBuilder, MessageComponentExt,
+    },
+};
+
+/// Custom ID of the select menu to pick a search result
+pub const SEARCH_PICK: &str = "search_pick";
+
+/// How many results the author can pick from
+const SEARCH_RESULTS: usize = 5;
+
+/// How long the author has to pick a result
+const PICK_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Discord's limit for labels and descriptions of select menu options
+const OPTION_TEXT_LEN: usize = 100;
+
+/// Let the author pick one of the top search results and return its url,
+/// `None` if there are no results or the author didn't pick in time
+pub async fn pick_search_result(
+    ctx: &Context,
+    command: &ApplicationCommand,
+    query: &str,
+) -> BotResult<Option<String>> {
+    let mut results = match ytdl::search(query, SEARCH_RESULTS).await {
+        Ok(results) if !results.is_empty() => results,
+        Ok(_) => {
+            let builder = MessageBuilder::new().embed("Didn't find any results");
+            command.update_message(ctx, builder).await?;
+
+            return Ok(None);
+        }
+        Err(why) => {
+            unwind_error!(warn, why, "Failed to search youtube for {}: {}", query);
+            let builder = MessageBuilder::new().error("Failed to search youtube!");
+            command.update_message(ctx, builder).await?;
+
+            return Ok(None);
+        }
+    };
+
+    let mut description = String::new();
+
+    for (result, i) in results.iter().zip(1..) {
+        let _ = write!(
+            description,
+            "`{}.` [{}]({})",
+            i,
+            result.title.as_deref().unwrap_or("<UNKNOWN>"),
+            result.url()
+        );
+
+        if let Some(details) = details(result) {
+            let _ = write!(description, " • {}", details);
+        }
+
+        description.push('\n');
+    }
+
+    let embed = EmbedBuilder::new()
+        .title("SEARCH RESULTS:")
+        .description(description);
+
+    let components = select_menu(&results);
+    let builder = MessageBuilder::from(embed).components(&components);
+    command.update_message(ctx, builder).await?;
+
+    let message_id = ctx
+        .http
+        .get_interaction_original(&command.token)?
+        .exec()
+        .await?
+        .model()
+        .await?
+        .id;
+
+    let author_id = command.user_id()?;
+
+    let mut components = ctx
+        .standby
+        .wait_for_event_stream(move |event: &Event| match event {
+            Event::InteractionCreate(e) => match e.0 {
+                Interaction::MessageComponent(ref component) => {
+                    component.message.id == message_id && component.data.custom_id == SEARCH_PICK
+                }
+                _ => false,
+            },
+            _ => false,
+        })
+        .filter_map(|event| async move {
+            match event {
+                Event::InteractionCreate(e) => match e.0 {
+                    Interaction::MessageComponent(component) => Some(component),
+                    _ => None,
+                },
+                _ => None,
+            }
+        })
+        .boxed();
+
+    let deadline = Instant::now() + PICK_TIMEOUT;
+
+    while let Ok(Some(component)) = time::timeout_at(deadline, components.next()).await {
+        if component.user_id().ok() != Some(author_id) {
+            let content = "Only the author of the command can pick a song";
+            let builder = MessageBuilder::new().error(content).ephemeral(true);
+            component.create_message(ctx, builder).await?;
+
+            continue;
+        }
+
+        let idx = component
+            .data
+            .values
+            .first()
+            .and_then(|value| value.parse::<usize>().ok())
+            .filter(|&idx| idx < results.len());
+
+        let result = match idx {
+            Some(idx) => results.swap_remove(idx),
+            None => continue,
+        };
+
+        let content = format!(
+            "Loading **{}**...",
+            result.title.as_deref().unwrap_or("<UNKNOWN>")
+        );
+
+        let builder = MessageBuilder::new().embed(content).components(&[]);
+        component.update_message(ctx, builder).await?;
+
+        return Ok(Some(result.url()));
+    }
+
+    let builder = MessageBuilder::new()
+        .embed("No song was picked in time")
+        .components(&[]);
+
+    command.update_message(ctx, builder).await?;
+
+    Ok(None)
+}
+
+/// Channel and duration of the result
+fn details(result: &PlaylistEntry) -> Option<String> {
+    let duration = result
+        .duration
+        .map(|secs| sec_to_minsec(secs as u32).to_string());
+
+    match (result.channel(), duration) {
+        (Some(channel), Some(duration)) => Some(format!("{} • `{}`", channel, duration)),
+        (Some(channel), None) => Some(channel.to_owned()),
+        (None, Some(duration)) => Some(format!("`{}`", duration)),
+        (None, None) => None,
+    }
+}
+
+fn select_menu(results: &[PlaylistEntry]) -> Vec<Component> {
+    let options = results
+        .iter()
+        .enumerate()
+        .map(|(i, result)| SelectMenuOption {
+            default: false,
+            description: details(result)
+                .map(|details| truncate(&details.replace('`', ""), OPTION_TEXT_LEN)),
+            emoji: None,
+            label: truncate(
+                &format!(
+                    "{}. {}",
+                    i + 1,
+                    result.title.as_deref().unwrap_or("<UNKNOWN>")
+                ),
+                OPTION_TEXT_LEN,
+            ),
+            value: i.to_string(),
+        })
+        .collect();
+
+    let menu = SelectMenu {
+        custom_id: SEARCH_PICK.to_owned(),
+        disabled: false,
+        max_values: Some(1),
+        min_values: Some(1),
+        options,
+        placeholder: Some("Pick a song".to_owned()),
+    };
+
+    vec![Component::ActionRow(ActionRow {
+        components: vec![Component::SelectMenu(menu)],
+    })]
+}
+
+pub fn truncate(text: &str, max: usize) -> String {
+    if text.chars().count() <= max {
+        return text.to_owned();
+    }
+
+    let mut truncated: String = text.chars().take(max - 3).collect();
+    truncated.push_str("...");
+
+    truncated
+}
@@ -0,0 +1,112 @@
+use std::{sync::Arc, time::Duration};
+
+use twilight_model::application::interaction::ApplicationCommand;
+
+use crate::{
+    commands::music::{ensure_same_channel, is_dj, requester, require_call},
+    context::Context,
+    error::BotResult,
+    utils::{
+        datetime::{parse_minsec, sec_to_minsec},
+        ApplicationCommandExt, MessageBuilder,
+    },
+};
+
+#[command]
+#[args = "SeekArgs"]
+#[description = "Jump to a position in the current song"]
+#[in_voice]
+pub struct Seek;
+
+#[derive(CommandArgs)]
+pub struct SeekArgs {
+    #[option(description = "Specify a position like 1:30, or relative to now like +30s or -10s")]
+    position: String,
+}
+
+pub async fn seek(ctx: Arc<Context>, command: ApplicationCommand, args: SeekArgs) -> BotResult<()> {
+    let SeekArgs { position } = args;
+    let author_id = command.user_id()?;
+    let guild_id = command.require_guild_id()?;
+
+    let call = match require_call(&ctx, &command).await? {
+        Some(call) => call,
+        None => return Ok(()),
+    };
+
+    let call = call.lock().await;
+    if let Err(content) = ensure_same_channel(&ctx, &call, guild_id, author_id) {
+        let builder = MessageBuilder::new().error(content);
+        return command.create_message(&ctx, builder).await;
+    }
+
+    let handle = match call.queue().current() {
+        Some(handle) => handle,
+        None => {
+            let builder = MessageBuilder::new().error("No song is currently playing!");
+            return command.create_message(&ctx, builder).await;
+        }
+    };
+
+    if !is_dj(&ctx, command.member.as_ref()) && requester(&handle).await != Some(author_id) {
+        let content = "You can only seek in your own songs unless you have the DJ role!";
+        let builder = MessageBuilder::new().error(content);
+        return command.create_message(&ctx, builder).await;
+    }
+
+    if !handle.is_seekable() {
+        let builder = MessageBuilder::new().error("This song doesn't support seeking!");
+        return command.create_message(&ctx, builder).await;
+    }
+
+    let current = handle.get_info().await?.position.as_secs() as u32;
+
+    let target = match seek_target(&position, current) {
+        Some(target) => target,
+        None => {
+            let content =
+                "Failed to parse the position, try something like `1:30`, `+30s` or `-10s`";
+            let builder = MessageBuilder::new().error(content);
+            return command.create_message(&ctx, builder).await;
+        }
+    };
+
+    let duration = handle.metadata().duration.map(|d| d.as_secs() as u32);
+
+    if let Some(duration) = duration.filter(|&duration| target >= duration) {
+        let content = format!("The song is only `{}` long!", sec_to_minsec(duration));
+        let builder = MessageBuilder::new().error(content);
+        return command.create_message(&ctx, builder).await;
+    }
+
+    info!(
+        "Seeking to {}s in current song of guild {}...",
+        target, guild_id
+    );
+    handle.seek_time(Duration::from_secs(target as u64))?;
+
+    let content = match duration {
+        Some(duration) => format!(
+            "Jumped to `{}` of `{}`!",
+            sec_to_minsec(target),
+            sec_to_minsec(duration)
+        ),
+        None => format!("Jumped to `{}`!", sec_to_minsec(target)),
+    };
+
+    let builder = MessageBuilder::new().embed(content);
+    command.create_message(&ctx, builder).await
+}
+
+/// Absolute position in seconds, offsets with a leading `+` or `-` are relative to `current`
+fn seek_target(input: &str, current: u32) -> Option<u32> {
+    let input = input.trim();
+
+    if let Some(offset) = input.strip_prefix('+') {
+        parse_minsec(offset).and_then(|secs| current.checked_add(secs))
+    } else if let Some(offset) = input.strip_prefix('-') {
+        parse_minsec(offset).map(|secs| current.saturating_sub(secs))
+    } else {
+        parse_minsec(input)
+    }
+}
@@ -0,0 +1,128 @@
+use std::sync::Arc;
+
+use twilight_model::application::interaction::ApplicationCommand;
+
+use crate::{
+    context::Context,
+    error::BotResult,
+    utils::{ApplicationCommandExt, MessageBuilder},
+};
+
+#[command]
+#[args = "SettingsArgs"]
+#[description = "Show or change the music settings of this server"]
+#[guild_only]
+#[required_permissions = "MANAGE_GUILD"]
+pub struct Settings;
+
+#[derive(CommandArgs)]
+pub struct SettingsArgs {
+    #[option(
+        description = "Specify the percentage of listeners needed to vote-skip a song",
+        min = 1,
+        max = 100
+    )]
+    vote_skip_percent: Option<u32>,
+    #[option(
+        description = "Specify how many songs can be added from a single playlist",
+        min = 1,
+        max = 500
+    )]
+    playlist_limit: Option<u32>,
+    #[option(
+        description = "Specify after how many minutes without music or listeners I leave, 0 to never leave",
+        min = 0,
+        max = 120
+    )]
+    idle_timeout: Option<u32>,
+    #[option(
+        description = "Specify the volume in percent that new songs start at",
+        min = 0,
+        max = 200
+    )]
+    volume: Option<u32>,
+    #[option(description = "Specify whether songs and TTS are adjusted to a similar loudness")]
+    normalize: Option<bool>,
+}
+
+pub async fn settings(
+    ctx: Arc<Context>,
+    command: ApplicationCommand,
+    args: SettingsArgs,
+) -> BotResult<()> {
+    let guild_id = command.require_guild_id()?;
+    let SettingsArgs {
+        vote_skip_percent,
+        playlist_limit,
+        idle_timeout,
+        volume,
+        normalize,
+    } = args;
+
+    let mut settings = ctx.guild_music_settings(guild_id);
+
+    if let Some(percent) = vote_skip_percent {
+        settings.vote_skip_percent = percent.clamp(1, 100) as u8;
+    }
+
+    if let Some(limit) = playlist_limit {
+        settings.playlist_limit = limit.clamp(1, 500) as u16;
+    }
+
+    if let Some(minutes) = idle_timeout {
+        settings.idle_timeout_mins = minutes.min(120) as u16;
+    }
+
+    if let Some(percent) = volume {
+        settings.volume_percent = percent.min(200) as u16;
+    }
+
+    if let Some(normalize) = normalize {
+        settings.normalize = normalize;
+    }
+
+    let changed = vote_skip_percent.is_some()
+        || playlist_limit.is_some()
+        || idle_timeout.is_some()
+        || volume.is_some()
+        || normalize.is_some();
+
+    if changed {
+        info!("Updating music settings of guild {}...", guild_id);
+
+        ctx.database
+            .upsert_music_settings(guild_id, &settings)
+            .await?;
+
+        ctx.music_settings.insert(guild_id, settings.clone());
+    }
+
+    let idle = match settings.idle_timeout_mins {
+        0 => "I never leave voice channels on my own".to_owned(),
+        1 => "I leave voice channels after 1 minute without music or listeners".to_owned(),
+        minutes => format!(
+            "I leave voice channels after {} minutes without music or listeners",
+            minutes
+        ),
+    };
+
+    let loudness = if settings.normalize {
+        "Songs and TTS are adjusted to a similar loudness"
+    } else {
+        "Songs and TTS play at their original loudness"
+    };
+
+    let content = format!(
+        "Skipping songs of other users requires votes from {}% of the listeners\n\
+        Playlists add up to {} songs\n{}\n\
+        New songs start at {}% volume\n{}",
+        settings.vote_skip_percent,
+        settings.playlist_limit,
+        idle,
+        settings.volume_percent,
+        loudness
+    );
+
+    let builder = MessageBuilder::new().embed(content);
+    command.create_message(&ctx, builder).await
+}
@@ -0,0 +1,206 @@
+use std::{
+    process::{Command, Stdio},
+    sync::Arc,
+    time::Duration,
+};
+
+use serde_json::Value;
+use songbird::input::{
+    children_to_reader,
+    error::{Error as InputError, Result as InputResult},
+    restartable::Restart,
+    Codec, Container, Input, Metadata, Restartable,
+};
+use tokio::process::Command as TokioCommand;
+use twilight_model::id::GuildId;
+
+use crate::{
+    context::Context,
+    utils::{matcher, ytdl::YOUTUBE_DL},
+};
+
+use super::filter::audio_filter;
+
+/// Same output format as songbird's own ytdl inputs
+const FFMPEG_ARGS: [&str; 9] = [
+    "-f",
+    "s16le",
+    "-ac",
+    "2",
+    "-ar",
+    "48000",
+    "-acodec",
+    "pcm_f32le",
+    "-",
+];
+
+/// Song that picks up the guild's audio filter whenever it's (re)started,
+/// e.g. when it begins to play or is seeked.
+/// Songs are always lazy so queued songs don't start ffmpeg with an outdated filter.
+struct FilteredSource {
+    ctx: Arc<Context>,
+    guild_id: GuildId,
+    url: String,
+    /// Audio files and songs of the music library are read by ffmpeg without youtube-dl
+    direct: bool,
+}
+
+/// Create a restartable song for a URL or a youtube-dl search like `ytsearch1:...`
+pub async fn ytdl_source(
+    ctx: &Arc<Context>,
+    guild_id: GuildId,
+    url: String,
+) -> InputResult<Restartable> {
+    let source = FilteredSource {
+        ctx: Arc::clone(ctx),
+        guild_id,
+        url,
+        direct: false,
+    };
+
+    Restartable::new(source, true).await
+}
+
+/// Links to audio files and songs of the music library are played directly,
+/// everything else goes through youtube-dl
+pub async fn song_source(ctx: &Arc<Context>, guild_id: GuildId, url: String) -> InputResult<Input> {
+    let local = ctx
+        .library
+        .as_ref()
+        .map_or(false, |library| library.contains(&url));
+
+    let direct = local || matcher::is_audio_file_url(&url);
+
+    let source = FilteredSource {
+        ctx: Arc::clone(ctx),
+        guild_id,
+        url,
+        direct,
+    };
+
+    Restartable::new(source, true).await.map(Input::from)
+}
+
+impl FilteredSource {
+    /// Metadata of the song and the input that ffmpeg plays it from
+    async fn resolve(&mut self, speed: f64) -> InputResult<(Metadata, String)> {
+        let (mut metadata, input) = if self.direct {
+            (self.probe().await?, self.url.clone())
+        } else {
+            self.ytdl().await?
+        };
+
+        metadata.duration = metadata.duration.map(|duration| duration.div_f64(speed));
+
+        Ok((metadata, input))
+    }
+
+    /// Metadata of a file that ffmpeg reads directly
+    async fn probe(&self) -> InputResult<Metadata> {
+        let output = TokioCommand::new("ffprobe")
+            .args(&["-v", "quiet", "-of", "json"])
+            .args(&["-show_format", "-show_streams", "-i"])
+            .arg(&self.url)
+            .stdin(Stdio::null())
+            .output()
+            .await?;
+
+        let value: Value =
+            serde_json::from_slice(&output.stdout).map_err(|error| InputError::Json {
+                error,
+                parsed_text: String::from_utf8_lossy(&output.stdout).into_owned(),
+            })?;
+
+        let mut metadata = Metadata::from_ffprobe_json(&value);
+
+        // Loops, saved queues and the history find the song again through its source
+        metadata.source_url = Some(self.url.clone());
+
+        Ok(metadata)
+    }
+
+    /// Metadata of the song and the URL of its audio stream
+    async fn ytdl(&mut self) -> InputResult<(Metadata, String)> {
+        let output = TokioCommand::new(YOUTUBE_DL)
+            .args(&["-j", "-f", "webm[abr>0]/bestaudio/best"])
+            .args(&["--no-playlist", "--ignore-config", "--no-warnings"])
+            .arg(&self.url)
+            .stdin(Stdio::null())
+            .output()
+            .await?;
+
+        if !output.status.success() {
+            return Err(InputError::YouTubeDlRun(output));
+        }
+
+        let value: Value =
+            serde_json::from_slice(&output.stdout).map_err(|error| InputError::Json {
+                error,
+                parsed_text: String::from_utf8_lossy(&output.stdout).into_owned(),
+            })?;
+
+        let stream_url = match value.get("url").and_then(Value::as_str) {
+            Some(url) => url.to_owned(),
+            None => return Err(InputError::YouTubeDlUrl(value)),
+        };
+
+        let metadata = Metadata::from_ytdl_output(value);
+
+        // Searches should restart the song they found instead of searching again
+        if let Some(ref url) = metadata.source_url {
+            self.url = url.clone();
+        }
+
+        Ok((metadata, stream_url))
+    }
+}
+
+#[async_trait]
+impl Restart for FilteredSource {
+    async fn call_restart(&mut self, time: Option<Duration>) -> InputResult<Input> {
+        let filter = audio_filter(&self.ctx, self.guild_id);
+        let speed = filter.map_or(1.0, |filter| filter.speed());
+        let (metadata, input) = self.resolve(speed).await?;
+
+        let mut ffmpeg = Command::new("ffmpeg");
+
+        // Local files would fail on the http options
+        if input.starts_with("http") {
+            ffmpeg.args(&["-reconnect", "1", "-reconnect_streamed", "1"]);
+        }
+
+        // Positions of the track are in filtered time, the stream is not
+        if let Some(time) = time {
+            let secs = time.as_secs_f64() * speed;
+            ffmpeg.arg("-ss").arg(format!("{:.3}", secs));
+        }
+
+        ffmpeg.arg("-i").arg(input);
+
+        if let Some(filter) = filter {
+            ffmpeg.arg("-af").arg(filter.ffmpeg_filter());
+        }
+
+        let child = ffmpeg
+            .args(&FFMPEG_ARGS)
+            .stdin(Stdio::null())
+            .stderr(Stdio::null())
+            .stdout(Stdio::piped())
+            .spawn()?;
+
+        Ok(Input::new(
+            true,
+            children_to_reader::<f32>(vec![child]),
+            Codec::FloatPcm,
+            Container::Raw,
+            Some(metadata),
+        ))
+    }
+
+    async fn lazy_init(&mut self) -> InputResult<(Option<Metadata>, Codec, Container)> {
+        let speed = audio_filter(&self.ctx, self.guild_id).map_or(1.0, |filter| filter.speed());
+        let (metadata, _) = self.resolve(speed).await?;
+
+        Ok((Some(metadata), Codec::FloatPcm, Container::Raw))
+    }
+}
@@ -0,0 +1,243 @@
+use std::{fmt::Write, sync::Arc, time::Duration};
+
+use songbird::{tracks::TrackHandle, typemap::TypeMapKey};
+use twilight_model::{
+    application::interaction::ApplicationCommand, channel::embed::EmbedField, id::GuildId,
+};
+
+use crate::{
+    commands::music::{enqueue, requester, song_source, track_voice},
+    context::Context,
+    database::HistoryEntry,
+    error::BotResult,
+    utils::{datetime::how_long_ago_dynamic, ApplicationCommandExt, EmbedBuilder, MessageBuilder},
+};
+
+use super::search::truncate;
+
+/// How many entries each list of the stats shows
+const LIST_SIZE: usize = 5;
+const RECENT_SIZE: usize = 10;
+const TITLE_LEN: usize = 60;
+
+/// Entry of the music history that belongs to a track, stored in the track's typemap
+#[derive(Copy, Clone)]
+struct HistoryId(i32);
+
+impl TypeMapKey for HistoryId {
+    type Value = HistoryId;
+}
+
+#[command]
+#[args = "StatsArgs"]
+#[description = "Show what this server listened to, or play a song of the history again"]
+pub struct Stats;
+
+#[derive(CommandArgs)]
+pub struct StatsArgs {
+    #[option(description = "Specify the number of a history entry to add it to the queue again")]
+    requeue: Option<i32>,
+}
+
+pub async fn stats(
+    ctx: Arc<Context>,
+    command: ApplicationCommand,
+    args: StatsArgs,
+) -> BotResult<()> {
+    let guild_id = command.require_guild_id()?;
+
+    if let Some(id) = args.requeue {
+        return requeue(ctx, command, guild_id, id).await;
+    }
+
+    let recent = ctx
+        .database
+        .get_recent_history(guild_id, RECENT_SIZE)
+        .await?;
+
+    if recent.is_empty() {
+        let builder = MessageBuilder::new().embed("No songs were played in this server yet!");
+        return command.create_message(&ctx, builder).await;
+    }
+
+    let listened = ctx.database.get_total_listened(guild_id).await?;
+    let top_songs = ctx.database.get_top_songs(guild_id, LIST_SIZE).await?;
+    let top_requesters = ctx.database.get_top_requesters(guild_id, LIST_SIZE).await?;
+
+    let mut songs = String::new();
+    for (song, i) in top_songs.iter().zip(1..) {
+        let _ = writeln!(
+            songs,
+            "`{}.` {} • {} play{}",
+            i,
+            song_link(&song.url, song.title.as_deref()),
+            song.plays,
+            if song.plays != 1 { "s" } else { "" }
+        );
+    }
+
+    let mut requesters = String::new();
+    for ((user_id, plays), i) in top_requesters.iter().zip(1..) {
+        let _ = writeln!(
+            requesters,
+            "`{}.` <@{}> • {} song{}",
+            i,
+            user_id,
+            plays,
+            if *plays != 1 { "s" } else { "" }
+        );
+    }
+
+    if requesters.is_empty() {
+        requesters.push_str("Nobody yet");
+    }
+
+    let hours = listened.as_secs_f64() / 3600.0;
+    let mut content = format!(
+        "This server listened to music for **{:.1}** hours\n\n**Recently played:**\n",
+        hours
+    );
+
+    for entry in recent.iter() {
+        let _ = write!(
+            content,
+            "`#{}` {} • {}",
+            entry.id,
+            song_link(&entry.url, entry.title.as_deref()),
+            how_long_ago_dynamic(&entry.played_at)
+        );
+
+        if let Some(user_id) = entry.requester {
+            let _ = write!(content, " • <@{}>", user_id);
+        }
+
+        content.push('\n');
+    }
+
+    content.push_str("\nUse the `requeue` option with a number like `#42` to play that song again");
+
+    let fields = vec![
+        EmbedField {
+            inline: false,
+            name: "Top songs".to_owned(),
+            value: songs,
+        },
+        EmbedField {
+            inline: false,
+            name: "Top requesters".to_owned(),
+            value: requesters,
+        },
+    ];
+
+    let embed = EmbedBuilder::new()
+        .description(content)
+        .fields(fields)
+        .title("MUSIC STATS:");
+
+    let builder = MessageBuilder::new().embed(embed);
+    command.create_message(&ctx, builder).await
+}
+
+async fn requeue(
+    ctx: Arc<Context>,
+    command: ApplicationCommand,
+    guild_id: GuildId,
+    id: i32,
+) -> BotResult<()> {
+    command.start_thinking(&ctx).await?;
+
+    let author_id = command.user_id()?;
+
+    let entry = match ctx.database.get_history_entry(guild_id, id).await? {
+        Some(entry) => entry,
+        None => {
+            let content = format!("There is no history entry `#{}` in this server!", id);
+            let builder = MessageBuilder::new().error(content);
+            return command.update_message(&ctx, builder).await;
+        }
+    };
+
+    let channel_id = match ctx
+        .cache
+        .voice_state(author_id, guild_id)
+        .and_then(|state| state.channel_id)
+    {
+        Some(id) => id,
+        None => {
+            let builder = MessageBuilder::new().error("You aren't in a voice channel!");
+            return command.update_message(&ctx, builder).await;
+        }
+    };
+
+    let (call_lock, result) = ctx.songbird.join(guild_id, channel_id).await;
+    result?;
+    track_voice(&ctx, guild_id, command.channel_id);
+
+    let HistoryEntry { url, title, .. } = entry;
+
+    let song = match song_source(&ctx, guild_id, url.clone()).await {
+        Ok(song) => song,
+        Err(why) => {
+            unwind_error!(warn, why, "Failed to load history entry {}: {}", url);
+            let builder = MessageBuilder::new().error("Failed to load the song!");
+            return command.update_message(&ctx, builder).await;
+        }
+    };
+
+    let mut call = call_lock.lock().await;
+    enqueue(&ctx, &mut call, song, author_id).await?;
+    drop(call);
+
+    let content = format!("Added {} to the queue", song_link(&url, title.as_deref()));
+    let builder = MessageBuilder::new().embed(content);
+    command.update_message(&ctx, builder).await
+}
+
+/// Titles are shortened to keep the embed within discord's limits
+fn song_link(url: &str, title: Option<&str>) -> String {
+    match title {
+        Some(title) => format!("[{}]({})", truncate(title, TITLE_LEN), url),
+        None => url.to_owned(),
+    }
+}
+
+/// Log the started song to the music history, songs without source like tts are skipped
+pub async fn log_track_start(ctx: &Context, guild_id: GuildId, handle: &TrackHandle) {
+    let metadata = handle.metadata();
+
+    let url = match metadata.source_url {
+        Some(ref url) => url,
+        None => return,
+    };
+
+    let requester = requester(handle).await;
+    let title = metadata.title.as_deref();
+    let artist = metadata.artist.as_deref();
+
+    match ctx
+        .database
+        .insert_history_entry(guild_id, url, title, artist, requester)
+        .await
+    {
+        Ok(id) => {
+            handle
+                .typemap()
+                .write()
+                .await
+                .insert::<HistoryId>(HistoryId(id));
+        }
+        Err(why) => unwind_error!(warn, why, "Failed to log song to the music history: {}"),
+    }
+}
+
+/// Store how long the song of a history entry was listened to
+pub async fn log_track_end(ctx: &Context, handle: &TrackHandle, listened: Duration) {
+    let id = match handle.typemap().read().await.get::<HistoryId>() {
+        Some(history_id) => history_id.0,
+        None => return,
+    };
+
+    if let Err(why) = ctx.database.update_history_listened(id, listened).await {
+        unwind_error!(warn, why, "Failed to update music history: {}");
+    }
+}
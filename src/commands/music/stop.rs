@@ -3,6 +3,7 @@ use std::sync::Arc;
 use twilight_model::application::interaction::ApplicationCommand;
 
 use crate::{
+    commands::music::{ensure_same_channel, is_dj, requester, require_call},
     context::Context,
     error::BotResult,
     utils::{ApplicationCommandExt, MessageBuilder},
@@ -10,43 +11,46 @@ use crate::{
 
 #[command]
 #[description = "Stop the currently playing song and clear the queue"]
+#[in_voice]
 pub struct Stop;
 
 pub async fn stop(ctx: Arc<Context>, command: ApplicationCommand) -> BotResult<()> {
     let author_id = command.user_id()?;
-    let guild_id = command.guild_id.expect("Missing Guild ID for play command");
-
-    if let Some(call) = ctx.songbird.get(command.guild_id.unwrap()) {
-        let call = call.lock().await;
-        let channel_opt = ctx
-            .cache
-            .voice_state(author_id, guild_id)
-            .and_then(|state| state.channel_id);
-
-        match (channel_opt, call.current_channel()) {
-            (Some(id1), Some(id2)) if id1.0 != id2.0 => {
-                let builder =
-                    MessageBuilder::new().error("You aren't in the same voice channel as me!");
-                return command.create_message(&ctx, builder).await;
-            }
-            (None, _) => {
-                let builder = MessageBuilder::new().error("You aren't in a voice channel!");
-                return command.create_message(&ctx, builder).await;
-            }
-            _ => {}
-        };
+    let guild_id = command.require_guild_id()?;
 
-        if call.queue().is_empty() {
-            let builder = MessageBuilder::new().error("No song is currently playing!");
-            return command.create_message(&ctx, builder).await;
-        }
+    let call = match require_call(&ctx, &command).await? {
+        Some(call) => call,
+        None => return Ok(()),
+    };
 
-        info!("Clearing song queue and stopping current song...");
-        call.queue().stop();
+    let call = call.lock().await;
+    if let Err(content) = ensure_same_channel(&ctx, &call, guild_id, author_id) {
+        let builder = MessageBuilder::new().error(content);
+        return command.create_message(&ctx, builder).await;
+    }
 
-        let builder = MessageBuilder::new().embed("Stopped playing music!");
+    if call.queue().is_empty() {
+        let builder = MessageBuilder::new().error("No song is currently playing!");
         return command.create_message(&ctx, builder).await;
     }
 
-    Ok(())
+    // Without being a DJ, the music can only be stopped if all songs are the author's
+    if !is_dj(&ctx, command.member.as_ref()) {
+        for handle in call.queue().current_queue() {
+            if requester(&handle).await != Some(author_id) {
+                let content = "You can only stop the music if all songs are yours \
+                    unless you have the DJ role!";
+                let builder = MessageBuilder::new().error(content);
+                return command.create_message(&ctx, builder).await;
+            }
+        }
+    }
+
+    info!("Clearing song queue and stopping current song...");
+    call.queue().stop();
+    ctx.loop_modes.remove(&guild_id);
+    ctx.audio_filters.remove(&guild_id);
+
+    let builder = MessageBuilder::new().embed("Stopped playing music!");
+    command.create_message(&ctx, builder).await
 }
@@ -0,0 +1,271 @@
+use std::sync::Arc;
+
+use songbird::{
+    input::Input,
+    tracks::{PlayMode, TrackHandle},
+    typemap::TypeMapKey,
+    Call, Event, EventContext, EventHandler, TrackEvent,
+};
+use tokio::sync::Mutex;
+use twilight_model::{
+    application::interaction::ApplicationCommand,
+    guild::{PartialMember, Permissions},
+    id::{ChannelId, GuildId, UserId},
+};
+
+use crate::{
+    context::Context,
+    error::BotResult,
+    utils::{spawn_guarded, ApplicationCommandExt, MessageBuilder},
+};
+
+use super::{
+    autoplay::autoplay_next,
+    idle::check_idle,
+    looping::{loop_mode, LoopMode},
+    loudness::apply_volume,
+    now_playing::refresh_panel,
+    previous::remember_track,
+    source::song_source,
+    stats::{log_track_end, log_track_start},
+};
+
+/// Members with a role of this name can manage all songs
+const DJ_ROLE_NAME: &str = "DJ";
+
+/// User that added a track to the queue, stored in the track's typemap
+#[derive(Copy, Clone)]
+pub struct Requester(pub UserId);
+
+impl TypeMapKey for Requester {
+    type Value = Requester;
+}
+
+/// Marks tracks that already started playing, resuming a paused track fires the play event again
+#[derive(Copy, Clone)]
+struct Started;
+
+impl TypeMapKey for Started {
+    type Value = Started;
+}
+
+/// Add the input to the queue at the guild's volume and attach the requester as well as the panel
+/// and loop handlers
+pub async fn enqueue(
+    ctx: &Arc<Context>,
+    call: &mut Call,
+    input: Input,
+    requester: UserId,
+) -> BotResult<TrackHandle> {
+    let guild_id = GuildId(call.guild_id().0);
+    let handle = call.enqueue_source(input);
+    handle.add_event(
+        Event::Track(TrackEvent::Play),
+        TrackStart(Arc::clone(ctx), guild_id),
+    )?;
+    handle.add_event(
+        Event::Track(TrackEvent::End),
+        TrackFinish(Arc::clone(ctx), guild_id),
+    )?;
+    set_requester(&handle, requester).await;
+
+    let source = handle.metadata().source_url.clone();
+    apply_volume(ctx, guild_id, &handle, source);
+
+    Ok(handle)
+}
+
+pub async fn set_requester(handle: &TrackHandle, requester: UserId) {
+    handle
+        .typemap()
+        .write()
+        .await
+        .insert::<Requester>(Requester(requester));
+}
+
+pub async fn requester(handle: &TrackHandle) -> Option<UserId> {
+    handle
+        .typemap()
+        .read()
+        .await
+        .get::<Requester>()
+        .map(|requester| requester.0)
+}
+
+/// Whether the member may manage songs of other users without a vote
+pub fn is_dj(ctx: &Context, member: Option<&PartialMember>) -> bool {
+    let member = match member {
+        Some(member) => member,
+        None => return false,
+    };
+
+    let permissions = member.permissions.unwrap_or_else(Permissions::empty);
+
+    if permissions.intersects(Permissions::ADMINISTRATOR | Permissions::MANAGE_CHANNELS) {
+        return true;
+    }
+
+    member.roles.iter().any(|&role_id| {
+        ctx.cache
+            .role(role_id)
+            .map_or(false, |role| role.name.eq_ignore_ascii_case(DJ_ROLE_NAME))
+    })
+}
+
+/// The guild's call, if the bot is not in a voice channel the author is told so instead
+pub async fn require_call(
+    ctx: &Context,
+    command: &ApplicationCommand,
+) -> BotResult<Option<Arc<Mutex<Call>>>> {
+    let guild_id = command.require_guild_id()?;
+
+    match ctx.songbird.get(guild_id) {
+        Some(call) => Ok(Some(call)),
+        None => {
+            let builder = MessageBuilder::new().error("I'm not in a voice channel!");
+            command.create_message(ctx, builder).await?;
+
+            Ok(None)
+        }
+    }
+}
+
+/// Commands that change the music may only be used from the bot's voice channel,
+/// the error describes why the author can't use them
+pub fn ensure_same_channel(
+    ctx: &Context,
+    call: &Call,
+    guild_id: GuildId,
+    author_id: UserId,
+) -> Result<(), &'static str> {
+    let channel_opt = ctx
+        .cache
+        .voice_state(author_id, guild_id)
+        .and_then(|state| state.channel_id);
+
+    match (channel_opt, call.current_channel()) {
+        (Some(id1), Some(id2)) if id1.0 != id2.0 => {
+            Err("You aren't in the same voice channel as me!")
+        }
+        (None, _) => Err("You aren't in a voice channel!"),
+        _ => Ok(()),
+    }
+}
+
+/// Amount of non-bot users in the voice channel
+pub fn count_listeners(ctx: &Context, channel_id: ChannelId) -> usize {
+    ctx.cache
+        .voice_channel_states(channel_id)
+        .map_or(0, |states| {
+            states
+                .into_iter()
+                .filter(|state| {
+                    !state
+                        .member
+                        .as_ref()
+                        .map_or(false, |member| member.user.bot)
+                })
+                .count()
+        })
+}
+
+/// Keeps the song looping if needed, logs it to the history and refreshes the now playing panel
+struct TrackStart(Arc<Context>, GuildId);
+
+#[async_trait]
+impl EventHandler for TrackStart {
+    async fn act(&self, ctx: &songbird::EventContext<'_>) -> Option<songbird::Event> {
+        if let EventContext::Track(&[(_, track)]) = ctx {
+            // Songs don't fire the play event again while looping
+            if loop_mode(&self.0, self.1) == LoopMode::Track {
+                if let Err(why) = track.enable_loop() {
+                    unwind_error!(warn, why, "Failed to loop song: {}");
+                }
+            }
+
+            // Track events are handled in order so the history entry exists once the song ends
+            if first_start(track).await {
+                remember_track(&self.0, self.1, track).await;
+                log_track_start(&self.0, self.1, track).await;
+            }
+
+            // Don't block other track events while the panel is written
+            let ctx = Arc::clone(&self.0);
+            let guild_id = self.1;
+
+            spawn_guarded(&self.0, check_idle(Arc::clone(&self.0), guild_id));
+
+            spawn_guarded(&self.0, async move {
+                if let Err(why) = refresh_panel(&ctx, guild_id).await {
+                    unwind_error!(warn, why, "Failed to refresh now playing panel: {}");
+                }
+            });
+        }
+
+        None
+    }
+}
+
+/// Whether the track starts for the first time instead of being resumed
+async fn first_start(handle: &TrackHandle) -> bool {
+    let mut typemap = handle.typemap().write().await;
+
+    if typemap.contains_key::<Started>() {
+        return false;
+    }
+
+    typemap.insert::<Started>(Started);
+
+    true
+}
+
+/// Stores how long finished songs were listened to and re-adds them to the end of the queue
+/// while the queue is looping, otherwise autoplay might add a related song
+struct TrackFinish(Arc<Context>, GuildId);
+
+#[async_trait]
+impl EventHandler for TrackFinish {
+    async fn act(&self, ctx: &songbird::EventContext<'_>) -> Option<songbird::Event> {
+        let (state, track) = match ctx {
+            EventContext::Track(&[(state, track)]) => (state, track),
+            _ => return None,
+        };
+
+        // The queue might have run out of songs
+        spawn_guarded(&self.0, check_idle(Arc::clone(&self.0), self.1));
+
+        log_track_end(&self.0, track, state.play_time).await;
+
+        // Skipped or removed songs are stopped instead of ending on their own
+        if state.playing != PlayMode::End {
+            return None;
+        }
+
+        if loop_mode(&self.0, self.1) != LoopMode::Queue {
+            let autoplay = autoplay_next(Arc::clone(&self.0), self.1, track.clone());
+            spawn_guarded(&self.0, autoplay);
+            return None;
+        }
+
+        let url = track.metadata().source_url.clone()?;
+        let requester = requester(track).await?;
+
+        let song = match song_source(&self.0, self.1, url).await {
+            Ok(song) => song,
+            Err(why) => {
+                unwind_error!(warn, why, "Failed to loop song: {}");
+                return None;
+            }
+        };
+
+        if let Some(call_lock) = self.0.songbird.get(self.1) {
+            let mut call = call_lock.lock().await;
+
+            if let Err(why) = enqueue(&self.0, &mut call, song, requester).await {
+                unwind_error!(warn, why, "Failed to loop song: {}");
+            }
+        }
+
+        None
+    }
+}
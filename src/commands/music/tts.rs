@@ -12,263 +12,135 @@ use tokio::{
     time::{self, Duration},
 };
 use twilight_model::{
-    application::{
-        command::{ChoiceCommandOptionData, CommandOption, CommandOptionChoice},
-        interaction::{
-            application_command::{CommandData, CommandDataOption},
-            ApplicationCommand,
-        },
-    },
+    application::interaction::ApplicationCommand,
     gateway::presence::{ActivityType, Status},
 };
 
 use crate::{
     context::Context,
-    error::BotResult,
+    error::{BotResult, Error},
     utils::{
         matcher, ApplicationCommandExt, BatchGetResponse, EmbedBuilder, MessageBuilder,
         SpeakResponse, SpeakStatusResponse, UBERDUCK_BASE,
     },
 };
 
+use super::{apply_volume, set_requester, track_voice};
+
 #[command]
 #[args = "TtsArgs"]
+#[bucket = "Tts"]
 #[description = "Say something in a chosen voice"]
-#[options = "tts_options"]
+#[in_voice]
 pub struct Tts;
 
+#[derive(CommandArgs)]
 pub struct TtsArgs {
+    #[option(description = "Specify what you want the bot to say")]
     text: String,
-    voice: String,
-}
-
-impl TtsArgs {
-    async fn parse_options(_: Arc<Context>, data: CommandData) -> BotResult<Self> {
-        let mut text = "".to_string();
-        let mut voice = "ye".to_string();
-        for option in data.options {
-            if let CommandDataOption::String { name, value } = option {
-                if name == "text" {
-                    text = value;
-                } else if name == "voice" {
-                    voice = value;
-                }
-            }
-        }
-        Ok(Self {
-            text: text.to_string(),
-            voice: voice.to_string(),
-        })
-    }
+    #[option(description = "Specify the voice the bot should use")]
+    voice: Voice,
 }
 
-fn tts_options() -> Vec<CommandOption> {
-    let text_data = ChoiceCommandOptionData {
-        choices: vec![],
-        description: "Specify what you want the bot to say".to_string(),
-        name: "text".to_string(),
-        required: true,
-    };
-
-    let voice_data = ChoiceCommandOptionData {
-        choices: vec![
-            CommandOptionChoice::String {
-                name: "Dwayne \"The Rock\" Johnson".to_string(),
-                value: "the-rock".to_string(),
-            },
-            CommandOptionChoice::String {
-                name: "Moistcr1tikal".to_string(),
-                value: "cr1tikal".to_string(),
-            },
-            CommandOptionChoice::String {
-                name: "BTMC".to_string(),
-                value: "btmc".to_string(),
-            },
-            CommandOptionChoice::String {
-                name: "Spongebob Squarepants".to_string(),
-                value: "spongebob".to_string(),
-            },
-            CommandOptionChoice::String {
-                name: "Matpat".to_string(),
-                value: "matpat".to_string(),
-            },
-            CommandOptionChoice::String {
-                name: "Kurzgesagt".to_string(),
-                value: "kurzgesagt".to_string(),
-            },
-            CommandOptionChoice::String {
-                name: "Kanye West".to_string(),
-                value: "ye".to_string(),
-            },
-            CommandOptionChoice::String {
-                name: "Goku".to_string(),
-                value: "goku".to_string(),
-            },
-            // CommandOptionChoice::String {
-            //     name: "Arnold Schwarzenegger".to_string(),
-            //     value: "arnold-schwarzenegger".to_string(),
-            // },
-            CommandOptionChoice::String {
-                name: "Peter Griffin".to_string(),
-                value: "peter-griffin".to_string(),
-            },
-            // CommandOptionChoice::String {
-            //     name: "Patrick".to_string(),
-            //     value: "patrick".to_string(),
-            // },
-            CommandOptionChoice::String {
-                name: "Eminem".to_string(),
-                value: "eminem".to_string(),
-            },
-            // CommandOptionChoice::String {
-            //     name: "Gordon Ramsay".to_string(),
-            //     value: "gordon-ramsay".to_string(),
-            // },
-            // CommandOptionChoice::String {
-            //     name: "Kermit The Frog".to_string(),
-            //     value: "kermit-the-frog".to_string(),
-            // },
-            // CommandOptionChoice::String {
-            //     name: "Leafyishere".to_string(),
-            //     value: "leafyishere".to_string(),
-            // },
-            // CommandOptionChoice::String {
-            //     name: "Mark Zuckerberg".to_string(),
-            //     value: "mark-zuckerberg".to_string(),
-            // },
-            // CommandOptionChoice::String {
-            //     name: "Vsauce".to_string(),
-            //     value: "michaelstevens".to_string(),
-            // },
-            CommandOptionChoice::String {
-                name: "Mickey Mouse".to_string(),
-                value: "mickey-mouse".to_string(),
-            },
-            // CommandOptionChoice::String {
-            //     name: "MrBeast".to_string(),
-            //     value: "mrbeast".to_string(),
-            // },
-            CommandOptionChoice::String {
-                name: "The Weeknd".to_string(),
-                value: "the-weeknd".to_string(),
-            },
-            // CommandOptionChoice::String {
-            //     name: "Walter White".to_string(),
-            //     value: "walter-white".to_string(),
-            // },
-            CommandOptionChoice::String {
-                name: "Ben Shapiro".to_string(),
-                value: "benshapiro".to_string(),
-            },
-            CommandOptionChoice::String {
-                name: "Cookie Masterson".to_string(),
-                value: "cookie-masterson".to_string(),
-            },
-            CommandOptionChoice::String {
-                name: "Morty".to_string(),
-                value: "morty".to_string(),
-            },
-            // CommandOptionChoice::String {
-            //     name: "Morgan Freeman".to_string(),
-            //     value: "morgan-freeman".to_string(),
-            // },
-            // CommandOptionChoice::String {
-            //     name: "Michael Caine".to_string(),
-            //     value: "michael-caine".to_string(),
-            // },
-            CommandOptionChoice::String {
-                name: "Siri".to_string(),
-                value: "siri-female-british".to_string(),
-            },
-            CommandOptionChoice::String {
-                name: "Benedict Cumberbatch".to_string(),
-                value: "benedict-cumberbatch".to_string(),
-            },
-            CommandOptionChoice::String {
-                name: "Alex Jones".to_string(),
-                value: "alex-jones".to_string(),
-            },
-            // CommandOptionChoice::String {
-            //     name: "Kratos".to_string(),
-            //     value: "kratos".to_string(),
-            // },
-            // CommandOptionChoice::String {
-            //     name: "Google Assistant".to_string(),
-            //     value: "google-assistant".to_string(),
-            // },
-            // CommandOptionChoice::String {
-            //     name: "Agent 47".to_string(),
-            //     value: "hitman-agent-47".to_string(),
-            // },
-            CommandOptionChoice::String {
-                name: "Stan Lee".to_string(),
-                value: "stan-lee".to_string(),
-            },
-            CommandOptionChoice::String {
-                name: "Naruto".to_string(),
-                value: "naruto-uzumaki".to_string(),
-            },
-            // CommandOptionChoice::String {
-            //     name: "Professor Layton".to_string(),
-            //     value: "layton".to_string(),
-            // },
-            // CommandOptionChoice::String {
-            //     name: "Al Michaels".to_string(),
-            //     value: "al-michaels".to_string(),
-            // },
-            // CommandOptionChoice::String {
-            //     name: "Kevin Harlan".to_string(),
-            //     value: "kevin-harlan".to_string(),
-            // },
-            // CommandOptionChoice::String {
-            //     name: "Marge Simpson".to_string(),
-            //     value: "marge-simpson".to_string(),
-            // },
-            // CommandOptionChoice::String {
-            //     name: "Homer Simpson".to_string(),
-            //     value: "homer-simpson".to_string(),
-            // },
-            // CommandOptionChoice::String {
-            //     name: "Cypher".to_string(),
-            //     value: "cypher-valorant".to_string(),
-            // },
-            CommandOptionChoice::String {
-                name: "3kliksphilip".to_string(),
-                value: "3kliksphilip".to_string(),
-            },
-            CommandOptionChoice::String {
-                name: "Linus Tech Tips".to_string(),
-                value: "linustt".to_string(),
-            },
-            CommandOptionChoice::String {
-                name: "Dhar Mann".to_string(),
-                value: "dharr-mann".to_string(),
-            },
-            CommandOptionChoice::String {
-                name: "GradeAUnderA".to_string(),
-                value: "gradeaundera".to_string(),
-            },
-            CommandOptionChoice::String {
-                name: "Pishifat".to_string(),
-                value: "pishifat".to_string(),
-            },
-        ],
-        description: "Specify the voice the bot should use".to_string(),
-        name: "voice".to_string(),
-        required: true,
-    };
-
-    vec![
-        CommandOption::String(text_data),
-        CommandOption::String(voice_data),
-    ]
+#[derive(CommandChoices)]
+pub enum Voice {
+    #[choice(name = "Dwayne \"The Rock\" Johnson", value = "the-rock")]
+    TheRock,
+    #[choice(name = "Moistcr1tikal", value = "cr1tikal")]
+    Cr1tikal,
+    #[choice(name = "BTMC", value = "btmc")]
+    Btmc,
+    #[choice(name = "Spongebob Squarepants", value = "spongebob")]
+    Spongebob,
+    #[choice(name = "Matpat", value = "matpat")]
+    Matpat,
+    #[choice(name = "Kurzgesagt", value = "kurzgesagt")]
+    Kurzgesagt,
+    #[choice(name = "Kanye West", value = "ye")]
+    Ye,
+    #[choice(name = "Goku", value = "goku")]
+    Goku,
+    // #[choice(name = "Arnold Schwarzenegger", value = "arnold-schwarzenegger")]
+    // ArnoldSchwarzenegger,
+    #[choice(name = "Peter Griffin", value = "peter-griffin")]
+    PeterGriffin,
+    // #[choice(name = "Patrick", value = "patrick")]
+    // Patrick,
+    #[choice(name = "Eminem", value = "eminem")]
+    Eminem,
+    // #[choice(name = "Gordon Ramsay", value = "gordon-ramsay")]
+    // GordonRamsay,
+    // #[choice(name = "Kermit The Frog", value = "kermit-the-frog")]
+    // KermitTheFrog,
+    // #[choice(name = "Leafyishere", value = "leafyishere")]
+    // Leafyishere,
+    // #[choice(name = "Mark Zuckerberg", value = "mark-zuckerberg")]
+    // MarkZuckerberg,
+    // #[choice(name = "Vsauce", value = "michaelstevens")]
+    // Vsauce,
+    #[choice(name = "Mickey Mouse", value = "mickey-mouse")]
+    MickeyMouse,
+    // #[choice(name = "MrBeast", value = "mrbeast")]
+    // Mrbeast,
+    #[choice(name = "The Weeknd", value = "the-weeknd")]
+    TheWeeknd,
+    // #[choice(name = "Walter White", value = "walter-white")]
+    // WalterWhite,
+    #[choice(name = "Ben Shapiro", value = "benshapiro")]
+    BenShapiro,
+    #[choice(name = "Cookie Masterson", value = "cookie-masterson")]
+    CookieMasterson,
+    #[choice(name = "Morty", value = "morty")]
+    Morty,
+    // #[choice(name = "Morgan Freeman", value = "morgan-freeman")]
+    // MorganFreeman,
+    // #[choice(name = "Michael Caine", value = "michael-caine")]
+    // MichaelCaine,
+    #[choice(name = "Siri", value = "siri-female-british")]
+    Siri,
+    #[choice(name = "Benedict Cumberbatch", value = "benedict-cumberbatch")]
+    BenedictCumberbatch,
+    #[choice(name = "Alex Jones", value = "alex-jones")]
+    AlexJones,
+    // #[choice(name = "Kratos", value = "kratos")]
+    // Kratos,
+    // #[choice(name = "Google Assistant", value = "google-assistant")]
+    // GoogleAssistant,
+    // #[choice(name = "Agent 47", value = "hitman-agent-47")]
+    // Agent47,
+    #[choice(name = "Stan Lee", value = "stan-lee")]
+    StanLee,
+    #[choice(name = "Naruto", value = "naruto-uzumaki")]
+    Naruto,
+    // #[choice(name = "Professor Layton", value = "layton")]
+    // ProfessorLayton,
+    // #[choice(name = "Al Michaels", value = "al-michaels")]
+    // AlMichaels,
+    // #[choice(name = "Kevin Harlan", value = "kevin-harlan")]
+    // KevinHarlan,
+    // #[choice(name = "Marge Simpson", value = "marge-simpson")]
+    // MargeSimpson,
+    // #[choice(name = "Homer Simpson", value = "homer-simpson")]
+    // HomerSimpson,
+    // #[choice(name = "Cypher", value = "cypher-valorant")]
+    // Cypher,
+    #[choice(name = "3kliksphilip", value = "3kliksphilip")]
+    Threekliksphilip,
+    #[choice(name = "Linus Tech Tips", value = "linustt")]
+    LinusTechTips,
+    #[choice(name = "Dhar Mann", value = "dharr-mann")]
+    DharMann,
+    #[choice(name = "GradeAUnderA", value = "gradeaundera")]
+    GradeAUnderA,
+    #[choice(name = "Pishifat", value = "pishifat")]
+    Pishifat,
 }
 
 pub async fn tts(ctx: Arc<Context>, command: ApplicationCommand, args: TtsArgs) -> BotResult<()> {
     command.start_thinking(&ctx).await?;
 
     let author_id = command.user_id()?;
-    let guild_id = command.guild_id.expect("Missing Guild ID for play command");
+    let guild_id = command.require_guild_id()?;
 
     let channel_id = match ctx
         .cache
@@ -290,6 +162,8 @@ pub async fn tts(ctx: Arc<Context>, command: ApplicationCommand, args: TtsArgs)
         return Err(success.into());
     }
 
+    track_voice(&ctx, guild_id, command.channel_id);
+
     info!(
         "Joined channel {} after tts command by {}",
         if let Some(channel) = ctx.cache.guild_channel(channel_id) {
@@ -305,19 +179,17 @@ pub async fn tts(ctx: Arc<Context>, command: ApplicationCommand, args: TtsArgs)
     let body = format!(
         "{{\"speech\": \"{}\", \"voice\": \"{}\"}}",
         text.cow_replace("\"", "\\\""),
-        voice
+        voice.value()
     );
     info!("{}", body);
+    let api_key =
+        env::var("UBERDUCK_API_KEY").map_err(|_| Error::MissingEnv("UBERDUCK_API_KEY"))?;
+    let secret = env::var("UBERDUCK_SECRET").map_err(|_| Error::MissingEnv("UBERDUCK_SECRET"))?;
+
     let bytes = ctx
         .client
         .post(req)
-        .basic_auth(
-            env::var("UBERDUCK_API_KEY").expect("Missing environment variable (UBERDUCK_API_KEY)."),
-            Some(
-                env::var("UBERDUCK_SECRET")
-                    .expect("Missing environment variable (UBERDUCK_SECRET)."),
-            ),
-        )
+        .basic_auth(api_key, Some(secret))
         .body(body)
         .send()
         .await?
@@ -366,7 +238,7 @@ pub async fn tts(ctx: Arc<Context>, command: ApplicationCommand, args: TtsArgs)
                         "Added TTS "
                     },
                     text,
-                    voice,
+                    voice.name(),
                     if empty { "" } else { " to the queue" },
                 );
 
@@ -374,11 +246,10 @@ pub async fn tts(ctx: Arc<Context>, command: ApplicationCommand, args: TtsArgs)
 
                 let mut builder = EmbedBuilder::new().description(content);
 
-                call.enqueue_source(input);
-                call.queue().modify_queue(|q| {
-                    q.back()
-                        .map(|q| q.add_event(Event::Track(TrackEvent::End), TrackEnd(path)))
-                });
+                let handle = call.enqueue_source(input);
+                apply_volume(&ctx, guild_id, &handle, Some(path.clone()));
+                handle.add_event(Event::Track(TrackEvent::End), TrackEnd(path))?;
+                set_requester(&handle, author_id).await;
                 command.update_message(&ctx, builder).await?;
 
                 // ctx.trackdata.write().replace(handle);
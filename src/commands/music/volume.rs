@@ -1,14 +1,9 @@
 use std::sync::Arc;
 
-use twilight_model::application::{
-    command::{ChoiceCommandOptionData, CommandOption},
-    interaction::{
-        application_command::{CommandData, CommandDataOption},
-        ApplicationCommand,
-    },
-};
+use twilight_model::application::interaction::ApplicationCommand;
 
 use crate::{
+    commands::music::{ensure_same_channel, normalization_gain, require_call},
     context::Context,
     error::BotResult,
     utils::{ApplicationCommandExt, MessageBuilder},
@@ -16,43 +11,19 @@ use crate::{
 
 #[command]
 #[args = "VolumeArgs"]
-#[description = "Change the volume of the current song"]
-#[options = "volume_options"]
+#[description = "Change the volume of the current song, normalized songs keep their loudness adjustment"]
+#[in_voice]
 pub struct Volume;
 
+#[derive(CommandArgs)]
 pub struct VolumeArgs {
+    #[option(
+        description = "Specify the volume where 1 is 100% (can be decimal)",
+        min = 0
+    )]
     volume: f32,
 }
 
-impl VolumeArgs {
-    async fn parse_options(_: Arc<Context>, data: CommandData) -> BotResult<Self> {
-        for option in data.options {
-            if let CommandDataOption::String { name, value } = option {
-                if name == "volume" {
-                    let value = value.parse::<f32>()?;
-                    return Ok(Self {
-                        volume: value.max(0.0),
-                    });
-                }
-            }
-        }
-
-        unreachable!()
-    }
-}
-
-fn volume_options() -> Vec<CommandOption> {
-    let option_data = ChoiceCommandOptionData {
-        choices: vec![],
-        description: "Specify the level to set the volume to (1 by default, can be decimal)"
-            .to_string(),
-        name: "volume".to_string(),
-        required: true,
-    };
-
-    vec![CommandOption::String(option_data)]
-}
-
 pub async fn volume(
     ctx: Arc<Context>,
     command: ApplicationCommand,
@@ -60,43 +31,32 @@ pub async fn volume(
 ) -> BotResult<()> {
     let VolumeArgs { volume } = args;
     let author_id = command.user_id()?;
-    let guild_id = command.guild_id.expect("Missing Guild ID for play command");
-
-    if let Some(call) = ctx.songbird.get(command.guild_id.unwrap()) {
-        let call = call.lock().await;
-        let channel_opt = ctx
-            .cache
-            .voice_state(author_id, guild_id)
-            .and_then(|state| state.channel_id);
-
-        match (channel_opt, call.current_channel()) {
-            (Some(id1), Some(id2)) if id1.0 != id2.0 => {
-                let builder =
-                    MessageBuilder::new().error("You aren't in the same voice channel as me!");
-                return command.create_message(&ctx, builder).await;
-            }
-            (None, _) => {
-                let builder = MessageBuilder::new().error("You aren't in a voice channel!");
-                return command.create_message(&ctx, builder).await;
-            }
-            _ => {}
-        };
+    let guild_id = command.require_guild_id()?;
 
-        let handle = match call.queue().current() {
-            Some(handle) => handle,
-            None => {
-                let builder = MessageBuilder::new().error("No song is currently playing!");
-                return command.create_message(&ctx, builder).await;
-            }
-        };
-
-        info!("Setting song volume to {}...", volume);
-        handle.set_volume(volume);
+    let call = match require_call(&ctx, &command).await? {
+        Some(call) => call,
+        None => return Ok(()),
+    };
 
-        let content = format!("Changed volume to {}!", volume);
-        let builder = MessageBuilder::new().embed(content);
+    let call = call.lock().await;
+    if let Err(content) = ensure_same_channel(&ctx, &call, guild_id, author_id) {
+        let builder = MessageBuilder::new().error(content);
         return command.create_message(&ctx, builder).await;
     }
 
-    Ok(())
+    let handle = match call.queue().current() {
+        Some(handle) => handle,
+        None => {
+            let builder = MessageBuilder::new().error("No song is currently playing!");
+            return command.create_message(&ctx, builder).await;
+        }
+    };
+
+    info!("Setting song volume to {}...", volume);
+    let gain = normalization_gain(&handle).await;
+    handle.set_volume(volume * gain)?;
+
+    let content = format!("Changed volume to {}!", volume);
+    let builder = MessageBuilder::new().embed(content);
+    command.create_message(&ctx, builder).await
 }
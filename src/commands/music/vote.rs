@@ -0,0 +1,354 @@
+use std::sync::Arc;
+
+use dashmap::mapref::entry::Entry;
+use hashbrown::HashSet;
+use songbird::{tracks::TrackHandle, Call};
+use tokio::time::{self, Duration};
+use twilight_model::{
+    application::{
+        component::{button::ButtonStyle, ActionRow, Button, Component},
+        interaction::{ApplicationCommand, MessageComponentInteraction},
+    },
+    channel::{embed::Embed, Message},
+    id::{ChannelId, GuildId, MessageId, UserId},
+};
+
+use crate::{
+    commands::music::{count_listeners, is_dj, requester},
+    context::Context,
+    error::{BotResult, Error},
+    utils::{
+        spawn_guarded, ApplicationCommandExt, EmbedBuilder, Footer, MessageBuilder,
+        MessageComponentExt,
+    },
+};
+
+use super::skip::{skip_tracks, skipped_content};
+
+/// Custom ID of the button to vote for skipping
+pub const VOTE_SKIP: &str = "vote_skip";
+
+/// Votes that don't gather enough voters within this time fail
+const VOTE_TIMEOUT: Duration = Duration::from_secs(60);
+
+pub struct SkipVote {
+    author: UserId,
+    channel_id: ChannelId,
+    message_id: MessageId,
+    /// Track that was playing when the vote started
+    track: TrackHandle,
+    amount: usize,
+    required: usize,
+    voters: HashSet<UserId>,
+}
+
+impl SkipVote {
+    fn embed(&self) -> Embed {
+        let content = format!(
+            "<@{}> wants to skip {} song{}\nVotes: {}/{}",
+            self.author,
+            self.amount,
+            if self.amount != 1 { "s" } else { "" },
+            self.voters.len(),
+            self.required
+        );
+
+        EmbedBuilder::new()
+            .description(content)
+            .footer(Footer::new(format!(
+                "The vote ends after {} seconds",
+                VOTE_TIMEOUT.as_secs()
+            )))
+            .build()
+    }
+}
+
+/// Open a vote to skip songs that the author can't skip on their own
+pub async fn start_vote(
+    ctx: Arc<Context>,
+    command: &ApplicationCommand,
+    call: &Call,
+    amount: usize,
+) -> BotResult<()> {
+    let author_id = command.user_id()?;
+    let guild_id = command.require_guild_id()?;
+
+    let track = match call.queue().current() {
+        Some(track) => track,
+        None => {
+            let builder = MessageBuilder::new().error("No song is currently playing!");
+            return command.create_message(&ctx, builder).await;
+        }
+    };
+
+    let listeners = call
+        .current_channel()
+        .map_or(0, |id| count_listeners(&ctx, ChannelId(id.0)));
+
+    let percent = ctx.guild_music_settings(guild_id).vote_skip_percent as usize;
+    let required = ((listeners * percent + 99) / 100).max(1);
+
+    // The author is the only one needed to pass the vote
+    if required <= 1 {
+        info!("Skipping {} song(s) in song queue...", amount);
+        skip_tracks(call, amount)?;
+
+        let builder = MessageBuilder::new().embed(skipped_content(amount));
+        return command.create_message(&ctx, builder).await;
+    }
+
+    let mut voters = HashSet::new();
+    voters.insert(author_id);
+
+    let vote = SkipVote {
+        author: author_id,
+        channel_id: command.channel_id,
+        message_id: MessageId(0),
+        track,
+        amount,
+        required,
+        voters,
+    };
+
+    info!(
+        "Starting vote to skip {} song(s) in guild {}, {} votes required",
+        amount, guild_id, required
+    );
+
+    let embed = vote.embed();
+
+    // Checking and inserting in one step so concurrent votes can't overwrite each other
+    match ctx.skip_votes.entry(guild_id) {
+        Entry::Occupied(_) => {
+            let builder = MessageBuilder::new().error("There is already a vote to skip going on!");
+            return command.create_message(&ctx, builder).await;
+        }
+        Entry::Vacant(entry) => {
+            entry.insert(vote);
+        }
+    }
+
+    let message = match send_vote(&ctx, command, embed).await {
+        Ok(message) => message,
+        Err(why) => {
+            ctx.skip_votes.remove(&guild_id);
+
+            return Err(why);
+        }
+    };
+
+    if let Some(mut vote) = ctx.skip_votes.get_mut(&guild_id) {
+        vote.message_id = message.id;
+    }
+
+    spawn_guarded(&ctx, vote_timeout(Arc::clone(&ctx), guild_id, message.id));
+
+    Ok(())
+}
+
+async fn send_vote(
+    ctx: &Context,
+    command: &ApplicationCommand,
+    embed: Embed,
+) -> BotResult<Message> {
+    let components = vote_components();
+    let builder = MessageBuilder::from(embed).components(&components);
+    command.create_message(ctx, builder).await?;
+
+    let message = ctx
+        .http
+        .get_interaction_original(&command.token)?
+        .exec()
+        .await?
+        .model()
+        .await?;
+
+    Ok(message)
+}
+
+pub async fn handle_vote(
+    ctx: Arc<Context>,
+    component: MessageComponentInteraction,
+) -> BotResult<()> {
+    let user_id = component.user_id()?;
+    let guild_id = component.guild_id.ok_or(Error::MissingGuild)?;
+
+    // Clone the track so the entry isn't held across any await points
+    let vote = ctx
+        .skip_votes
+        .get(&guild_id)
+        .filter(|vote| vote.message_id == component.message.id)
+        .map(|vote| (vote.track.clone(), vote.amount));
+
+    let (track, amount) = match vote {
+        Some(vote) => vote,
+        None => {
+            let builder = MessageBuilder::new()
+                .error("This vote has already ended")
+                .ephemeral(true);
+            return component.create_message(&ctx, builder).await;
+        }
+    };
+
+    let call_lock = match ctx.songbird.get(guild_id) {
+        Some(call) => call,
+        None => {
+            ctx.skip_votes.remove(&guild_id);
+            let builder = MessageBuilder::new()
+                .error("I'm not in a voice channel anymore!")
+                .ephemeral(true);
+            return component.create_message(&ctx, builder).await;
+        }
+    };
+
+    let (bot_channel, queue) = {
+        let call = call_lock.lock().await;
+
+        (call.current_channel(), call.queue().current_queue())
+    };
+
+    let user_channel = ctx
+        .cache
+        .voice_state(user_id, guild_id)
+        .and_then(|state| state.channel_id);
+
+    match (user_channel, bot_channel) {
+        (Some(id1), Some(id2)) if id1.0 == id2.0 => {}
+        _ => {
+            let builder = MessageBuilder::new()
+                .error("You need to be in my voice channel to vote!")
+                .ephemeral(true);
+            return component.create_message(&ctx, builder).await;
+        }
+    }
+
+    // DJs and users who requested all songs that would be skipped decide on their own
+    let skipped = &queue[..amount.min(queue.len())];
+    let bypass = is_dj(&ctx, component.member.as_ref()) || requested_all(skipped, user_id).await;
+
+    let update = match ctx.skip_votes.get_mut(&guild_id) {
+        Some(mut vote) if vote.message_id == component.message.id => {
+            let new_voter = vote.voters.insert(user_id);
+            let passed = bypass || vote.voters.len() >= vote.required;
+
+            Some((new_voter, passed, vote.amount, vote.embed()))
+        }
+        _ => None,
+    };
+
+    let (passed, amount, embed) = match update {
+        Some((false, false, ..)) => {
+            let builder = MessageBuilder::new()
+                .error("You already voted!")
+                .ephemeral(true);
+            return component.create_message(&ctx, builder).await;
+        }
+        Some((_, passed, amount, embed)) => (passed, amount, embed),
+        None => {
+            let builder = MessageBuilder::new()
+                .error("This vote has already ended")
+                .ephemeral(true);
+            return component.create_message(&ctx, builder).await;
+        }
+    };
+
+    if !passed {
+        let components = vote_components();
+        let builder = MessageBuilder::from(embed).components(&components);
+        return component.update_message(&ctx, builder).await;
+    }
+
+    let removed = ctx
+        .skip_votes
+        .remove_if(&guild_id, |_, vote| vote.message_id == component.message.id);
+
+    // Another vote passed it in the meantime
+    if removed.is_none() {
+        return Ok(());
+    }
+
+    let call = call_lock.lock().await;
+
+    // Don't skip the following songs if the voted song already finished
+    let content = match call.queue().current() {
+        Some(current) if current.uuid() == track.uuid() => {
+            info!("Vote passed, skipping {} song(s)...", amount);
+            skip_tracks(&call, amount)?;
+
+            format!("Vote passed! {}", skipped_content(amount))
+        }
+        _ => "The song already ended before the vote passed".to_owned(),
+    };
+
+    drop(call);
+
+    let builder = MessageBuilder::new().embed(content).components(&[]);
+
+    component.update_message(&ctx, builder).await
+}
+
+async fn requested_all(handles: &[TrackHandle], user_id: UserId) -> bool {
+    for handle in handles {
+        if requester(handle).await != Some(user_id) {
+            return false;
+        }
+    }
+
+    true
+}
+
+/// End the vote as failed if it's still going on after the timeout
+async fn vote_timeout(ctx: Arc<Context>, guild_id: GuildId, message_id: MessageId) {
+    time::sleep(VOTE_TIMEOUT).await;
+
+    let vote = match ctx
+        .skip_votes
+        .remove_if(&guild_id, |_, vote| vote.message_id == message_id)
+    {
+        Some((_, vote)) => vote,
+        None => return,
+    };
+
+    let content = format!(
+        "Vote failed, only {}/{} listeners voted to skip",
+        vote.voters.len(),
+        vote.required
+    );
+
+    if let Err(why) = end_vote(&ctx, vote.channel_id, message_id, content).await {
+        unwind_error!(warn, why, "Failed to end skip vote: {}");
+    }
+}
+
+async fn end_vote(
+    ctx: &Context,
+    channel_id: ChannelId,
+    message_id: MessageId,
+    content: String,
+) -> BotResult<()> {
+    let embed = EmbedBuilder::new().description(content).build();
+
+    ctx.http
+        .update_message(channel_id, message_id)
+        .embeds(&[embed])?
+        .components(Some(&[]))?
+        .exec()
+        .await?;
+
+    Ok(())
+}
+
+fn vote_components() -> Vec<Component> {
+    let button = Component::Button(Button {
+        custom_id: Some(VOTE_SKIP.to_owned()),
+        disabled: false,
+        emoji: None,
+        label: Some("Vote to skip".to_owned()),
+        style: ButtonStyle::Primary,
+        url: None,
+    });
+
+    vec![Component::ActionRow(ActionRow {
+        components: vec![button],
+    })]
+}
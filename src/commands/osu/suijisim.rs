@@ -11,7 +11,7 @@ use twilight_model::{
 
 use crate::{
     context::Context,
-    error::BotResult,
+    error::{BotResult, Error},
     utils::{
         ApplicationCommandExt, BatchGetResponse, EmbedBuilder, MessageBuilder, SPREADSHEET_BASE,
         SUIJI_SPREADSHEET_ID,
@@ -19,6 +19,7 @@ use crate::{
 };
 
 #[command]
+#[cooldown = "10"]
 #[description = "Simulate a possible iteration of the suiji bracket"]
 pub struct Suijisim;
 
@@ -27,7 +28,7 @@ async fn suijisim(ctx: Arc<Context>, command: ApplicationCommand) -> BotResult<(
         "{}{}/values:batchGet?ranges=Registrations!N10:N137&ranges=Registrations!N138:N265&ranges=Registrations!N266:N393&ranges=Registrations!N394:N521&key={}",
         SPREADSHEET_BASE,
         SUIJI_SPREADSHEET_ID,
-        env::var("GOOGLE_API_KEY").expect("Missing environment variable (GOOGLE_API_KEY).")
+        env::var("GOOGLE_API_KEY").map_err(|_| Error::MissingEnv("GOOGLE_API_KEY"))?
     );
     let bytes = reqwest::get(req).await?.bytes().await?;
     info!("{}", String::from_utf8_lossy(&bytes));
@@ -37,24 +38,25 @@ async fn suijisim(ctx: Arc<Context>, command: ApplicationCommand) -> BotResult<(
         .into_iter()
         .flat_map(|vr| vr.values.into_iter().flatten().collect::<Vec<_>>());
 
-    let mut iter = players.step_by(1);
-    let mut res = Vec::with_capacity(4);
+    let mut pots: Vec<Vec<_>> = (0..4)
+        .map(|_| players.by_ref().take(64).collect())
+        .collect();
 
-    for _ in 0..4 {
-        res.push(iter.by_ref().take(64).collect::<Vec<_>>());
+    if pots.iter().any(|pot| pot.len() < 64) {
+        let builder = MessageBuilder::new().error("Not enough registrations to fill all pots");
+        return command.create_message(&ctx, builder).await;
     }
 
-    let [a, b, c, d] = dbg!(res.as_mut_slice()) else {
-        unreachable!()
-    };
     {
         let mut rng = rand::thread_rng();
 
-        a.shuffle(&mut rng);
-        b.shuffle(&mut rng);
-        c.shuffle(&mut rng);
-        d.shuffle(&mut rng);
+        for pot in pots.iter_mut() {
+            pot.shuffle(&mut rng);
+        }
     }
+
+    let (a, b, c, d) = (&pots[0], &pots[1], &pots[2], &pots[3]);
+
     let mut fields1: Vec<EmbedField> = (0..16)
         .map(|idx| EmbedField {
             inline: true,
@@ -0,0 +1,194 @@
+use std::fmt::Write;
+
+use twilight_model::{
+    application::{
+        callback::{CallbackData, InteractionResponse},
+        interaction::{application_command::CommandDataOption, ApplicationCommand},
+    },
+    channel::{embed::EmbedField, message::MessageFlags},
+    id::{GuildId, InteractionId},
+};
+
+use crate::{
+    context::Context,
+    error::{BotResult, Error},
+    utils::{EmbedBuilder, DESCRIPTION_SIZE, FIELD_VALUE_SIZE, RED},
+};
+
+/// Everything needed to report a failed command after it has been consumed
+pub struct CommandReport {
+    interaction_id: InteractionId,
+    token: String,
+    path: String,
+    args: String,
+    guild_id: Option<GuildId>,
+}
+
+impl CommandReport {
+    pub fn new(command: &ApplicationCommand, path: String) -> Self {
+        let mut args = String::new();
+        write_options(&command.data.options, &mut args);
+
+        Self {
+            interaction_id: command.id,
+            token: command.token.clone(),
+            path,
+            args,
+            guild_id: command.guild_id,
+        }
+    }
+
+    /// Log the error under a short ID, tell the author about it, and forward it to the error channel
+    pub async fn report(self, ctx: &Context, why: Error) {
+        let error_id = format!("{:06x}", rand::random::<u32>() & 0xFF_FF_FF);
+
+        unwind_error!(
+            error,
+            why,
+            "[{}] Failed to process /{} (args: {}): {}",
+            error_id,
+            self.path,
+            self.args
+        );
+
+        if let Err(err) = self.notify_author(ctx, &why, &error_id).await {
+            unwind_error!(
+                warn,
+                err,
+                "[{}] Failed to notify author about error: {}",
+                error_id
+            );
+        }
+
+        if let Err(err) = self.forward(ctx, &why, &error_id).await {
+            unwind_error!(warn, err, "[{}] Failed to forward error: {}", error_id);
+        }
+    }
+
+    async fn notify_author(&self, ctx: &Context, error: &Error, error_id: &str) -> BotResult<()> {
+        let content = format!("{}\nError ID: `{}`", error.user_message(), error_id);
+        let embed = EmbedBuilder::new().description(content).color(RED).build();
+
+        let response = InteractionResponse::ChannelMessageWithSource(CallbackData {
+            allowed_mentions: None,
+            components: None,
+            content: None,
+            embeds: vec![embed.clone()],
+            flags: Some(MessageFlags::EPHEMERAL),
+            tts: None,
+        });
+
+        let callback = ctx
+            .http
+            .interaction_callback(self.interaction_id, &self.token, &response)
+            .exec()
+            .await;
+
+        // The command might have responded already, e.g. by thinking
+        if callback.is_err() {
+            ctx.http
+                .create_followup_message(&self.token)?
+                .embeds(&[embed])
+                .ephemeral(true)
+                .exec()
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn forward(&self, ctx: &Context, error: &Error, error_id: &str) -> BotResult<()> {
+        let channel_id = match ctx.error_channel {
+            Some(channel_id) => channel_id,
+            None => return Ok(()),
+        };
+
+        let mut description = format!("```\n{}", error);
+        let mut err: &dyn std::error::Error = error;
+
+        while let Some(source) = err.source() {
+            let _ = write!(description, "\n  - caused by: {}", source);
+            err = source;
+        }
+
+        truncate(&mut description, DESCRIPTION_SIZE - 4);
+        description.push_str("\n```");
+
+        let guild = match self.guild_id {
+            Some(guild_id) => match ctx.cache.guild(guild_id) {
+                Some(guild) => format!("{} ({})", guild.name, guild_id),
+                None => guild_id.to_string(),
+            },
+            None => "Private".to_owned(),
+        };
+
+        let mut args = if self.args.is_empty() {
+            "-".to_owned()
+        } else {
+            self.args.clone()
+        };
+
+        truncate(&mut args, FIELD_VALUE_SIZE);
+
+        let fields = vec![
+            field("Command", format!("/{}", self.path)),
+            field("Guild", guild),
+            field("Arguments", args),
+        ];
+
+        let embed = EmbedBuilder::new()
+            .title(format!("Error `{}`", error_id))
+            .description(description)
+            .fields(fields)
+            .color(RED)
+            .build();
+
+        ctx.http
+            .create_message(channel_id)
+            .embeds(&[embed])?
+            .exec()
+            .await?;
+
+        Ok(())
+    }
+}
+
+fn write_options(options: &[CommandDataOption], args: &mut String) {
+    for option in options {
+        match option {
+            CommandDataOption::SubCommand { options, .. } => write_options(options, args),
+            CommandDataOption::String { name, value } => push_arg(args, name, value),
+            CommandDataOption::Integer { name, value } => push_arg(args, name, value),
+            CommandDataOption::Boolean { name, value } => push_arg(args, name, value),
+        }
+    }
+}
+
+fn push_arg(args: &mut String, name: &str, value: impl std::fmt::Display) {
+    if !args.is_empty() {
+        args.push(' ');
+    }
+
+    let _ = write!(args, "{}={}", name, value);
+}
+
+fn field(name: &str, value: String) -> EmbedField {
+    EmbedField {
+        inline: false,
+        name: name.to_owned(),
+        value,
+    }
+}
+
+fn truncate(content: &mut String, max: usize) {
+    if content.len() > max {
+        let mut end = max - 3;
+
+        while !content.is_char_boundary(end) {
+            end -= 1;
+        }
+
+        content.truncate(end);
+        content.push_str("...");
+    }
+}
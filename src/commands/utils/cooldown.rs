@@ -0,0 +1,135 @@
+use std::sync::Arc;
+
+use twilight_model::application::interaction::ApplicationCommand;
+
+use crate::{
+    buckets::{BucketScope, Ratelimit},
+    context::Context,
+    database::BucketOverride,
+    error::BotResult,
+    utils::{ApplicationCommandExt, MessageBuilder},
+};
+
+#[command]
+#[args = "CooldownArgs"]
+#[description = "Change how often commands of a ratelimit bucket can be used in this server"]
+#[guild_only]
+#[required_permissions = "MANAGE_GUILD"]
+pub struct Cooldown;
+
+#[derive(CommandArgs)]
+pub struct CooldownArgs {
+    #[option(description = "Specify the ratelimit bucket")]
+    bucket: CooldownBucket,
+    #[option(description = "Specify whether the limit applies per user or to the whole server")]
+    scope: CooldownScope,
+    #[option(
+        description = "Specify the seconds between two uses",
+        min = 0,
+        max = 86400
+    )]
+    delay: u32,
+    #[option(
+        description = "Specify the seconds of a window that allows at most `uses` uses",
+        min = 1,
+        max = 86400
+    )]
+    window: Option<u32>,
+    #[option(
+        description = "Specify how often the commands can be used within `window`",
+        min = 1,
+        max = 1000
+    )]
+    uses: Option<u32>,
+}
+
+/// Values must match `BucketName::name`
+#[derive(Copy, Clone, CommandChoices)]
+pub enum CooldownBucket {
+    #[choice(name = "Message archive")]
+    Archive,
+    #[choice(name = "TTS")]
+    Tts,
+    #[choice(name = "/activity")]
+    Activity,
+    #[choice(name = "/suijisim")]
+    Suijisim,
+}
+
+/// The global scope is shared by all servers so it can't be overridden
+#[derive(Copy, Clone, CommandChoices)]
+pub enum CooldownScope {
+    User,
+    #[choice(name = "Server", value = "guild")]
+    Guild,
+}
+
+pub async fn cooldown(
+    ctx: Arc<Context>,
+    command: ApplicationCommand,
+    args: CooldownArgs,
+) -> BotResult<()> {
+    let guild_id = command.require_guild_id()?;
+
+    let CooldownArgs {
+        bucket,
+        scope,
+        delay,
+        window,
+        uses,
+    } = args;
+
+    let limit = match (window, uses) {
+        (Some(window), Some(uses)) => Some((window as u64, uses)),
+        (None, None) => None,
+        _ => {
+            let content = "You need to specify both `window` and `uses` or neither of them!";
+            let builder = MessageBuilder::new().error(content);
+            return command.create_message(&ctx, builder).await;
+        }
+    };
+
+    let scope = match scope {
+        CooldownScope::User => BucketScope::User,
+        CooldownScope::Guild => BucketScope::Guild,
+    };
+
+    let entry = BucketOverride {
+        guild_id,
+        bucket: bucket.value().to_owned(),
+        scope,
+        ratelimit: Ratelimit::new(delay as u64, limit),
+    };
+
+    info!(
+        "Overriding {} limit of bucket `{}` in guild {}...",
+        scope, entry.bucket, guild_id
+    );
+
+    ctx.database.upsert_bucket_override(&entry).await?;
+
+    let BucketOverride {
+        bucket, ratelimit, ..
+    } = entry;
+
+    ctx.buckets.set_override(guild_id, bucket, scope, ratelimit);
+
+    let mut content = format!(
+        "Commands of this bucket can be used every {} second{} per {}",
+        delay,
+        if delay != 1 { "s" } else { "" },
+        scope
+    );
+
+    if let Some((window, uses)) = limit {
+        content.push_str(&format!(
+            "\nAt most {} time{} within {} seconds",
+            uses,
+            if uses != 1 { "s" } else { "" },
+            window
+        ));
+    }
+
+    let builder = MessageBuilder::new().embed(content);
+    command.create_message(&ctx, builder).await
+}
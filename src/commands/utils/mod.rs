@@ -1,5 +1,7 @@
+mod cooldown;
 mod ping;
 mod roll;
 
+pub use cooldown::Cooldown;
 pub use ping::Ping;
 pub use roll::Roll;
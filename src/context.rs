@@ -1,8 +1,16 @@
-use std::sync::Arc;
+use std::{collections::VecDeque, sync::Arc};
 
-use crate::{osu_irc::IrcClient, stats::BotStats};
-use crate::{BotResult, Database};
+use crate::{
+    buckets::Buckets, library::MusicLibrary, lyrics::LyricsProvider, osu_irc::IrcClient,
+    pagination::PaginationState, stats::BotStats,
+};
+use crate::{
+    commands::{AudioFilter, IdleTracker, LoopMode, NowPlayingPanel, PlayedTrack, SkipVote},
+    database::MusicSettings,
+    BotResult, Database,
+};
 
+use dashmap::DashMap;
 use hashbrown::HashSet;
 use parking_lot::RwLock;
 use reqwest::Client;
@@ -13,25 +21,53 @@ use twilight_gateway::Cluster;
 use twilight_http::Client as HttpClient;
 use twilight_model::gateway::payload::UpdatePresence;
 use twilight_model::gateway::presence::{Activity, ActivityType, Status};
-use twilight_model::id::GuildId;
+use twilight_model::id::{ChannelId, GuildId, MessageId};
 use twilight_standby::Standby;
 
 pub struct Context {
+    /// Audio filter per guild, absent if songs play unaltered
+    pub audio_filters: DashMap<GuildId, AudioFilter>,
+    pub buckets: Buckets,
     pub cache: InMemoryCache,
     pub client: Client,
     pub database: Database,
+    /// Channel that failed commands are forwarded to
+    pub error_channel: Option<ChannelId>,
     pub osu: OsuClient,
     pub irc: IrcClient,
+    /// Local music directory, absent if none is configured
+    pub library: Option<MusicLibrary>,
     pub cluster: Cluster,
     pub http: HttpClient,
+    /// Voice connections per guild that are left once idle for too long
+    pub idle_trackers: DashMap<GuildId, IdleTracker>,
+    /// Current loop mode per guild, absent if nothing is looping
+    pub loop_modes: DashMap<GuildId, LoopMode>,
+    /// Measured loudness in LUFS per song source, used to normalize songs
+    pub loudness: DashMap<String, f32>,
+    pub lyrics: Box<dyn LyricsProvider>,
+    pub music_settings: DashMap<GuildId, MusicSettings>,
+    /// Most recent now playing panel per guild
+    pub now_playing: DashMap<GuildId, NowPlayingPanel>,
+    pub paginations: DashMap<MessageId, PaginationState>,
+    /// Recently started songs per guild, newest last
+    pub played_tracks: DashMap<GuildId, VecDeque<PlayedTrack>>,
     // pub hub: Sheets,
     pub servers: RwLock<HashSet<GuildId>>,
+    /// Ongoing vote to skip songs per guild
+    pub skip_votes: DashMap<GuildId, SkipVote>,
     pub songbird: Songbird,
     pub standby: Standby,
     pub stats: BotStats,
 }
 
 impl Context {
+    pub fn guild_music_settings(&self, guild_id: GuildId) -> MusicSettings {
+        self.music_settings
+            .get(&guild_id)
+            .map_or_else(MusicSettings::default, |settings| settings.clone())
+    }
+
     pub async fn set_shard_activity(
         &self,
         shard_id: u64,
@@ -40,7 +76,7 @@ impl Context {
         message: impl Into<String>,
     ) -> BotResult<()> {
         let activities = vec![generate_activity(activity_type, message.into())];
-        let status = UpdatePresence::new(activities, false, None, status).unwrap();
+        let status = UpdatePresence::new(activities, false, None, status)?;
         self.cluster.command(shard_id, &status).await?;
 
         Ok(())
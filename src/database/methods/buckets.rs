@@ -0,0 +1,56 @@
+use futures::StreamExt;
+use twilight_model::id::GuildId;
+
+use crate::{
+    buckets::Ratelimit,
+    database::{BucketOverride, Database},
+    error::BotResult,
+};
+
+impl Database {
+    pub async fn get_bucket_overrides(&self) -> BotResult<Vec<BucketOverride>> {
+        let mut stream = sqlx::query!("SELECT * FROM bucket_overrides;").fetch(&self.pool);
+        let mut overrides = Vec::new();
+        while let Some(entry) = stream.next().await.transpose()? {
+            let scope = match entry.scope.parse() {
+                Ok(scope) => scope,
+                Err(_) => {
+                    warn!("Unknown bucket scope `{}` in database", entry.scope);
+                    continue;
+                }
+            };
+
+            let limit = entry
+                .limit_window
+                .zip(entry.limit_uses)
+                .map(|(window, uses)| (window.max(0) as u64, uses.max(0) as u32));
+
+            overrides.push(BucketOverride {
+                guild_id: GuildId(entry.guild_id as u64),
+                bucket: entry.bucket,
+                scope,
+                ratelimit: Ratelimit::new(entry.delay.max(0) as u64, limit),
+            });
+        }
+        Ok(overrides)
+    }
+
+    pub async fn upsert_bucket_override(&self, entry: &BucketOverride) -> BotResult<()> {
+        let (window, uses) = match entry.ratelimit.limit {
+            Some((window, uses)) => (Some(window as i32), Some(uses as i32)),
+            None => (None, None),
+        };
+
+        let query = sqlx::query!(
+            "INSERT INTO bucket_overrides (guild_id, bucket, scope, delay, limit_window, limit_uses) VALUES ($1, $2, $3, $4, $5, $6) ON CONFLICT (guild_id, bucket, scope) DO UPDATE SET delay = $4, limit_window = $5, limit_uses = $6;",
+            entry.guild_id.0 as i64,
+            entry.bucket,
+            entry.scope.to_string(),
+            entry.ratelimit.delay as i32,
+            window,
+            uses
+        );
+        query.execute(&self.pool).await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,65 @@
+use std::time::Duration;
+
+use futures::StreamExt;
+
+use crate::{
+    database::{Database, LibraryTrack},
+    error::BotResult,
+};
+
+impl Database {
+    pub async fn upsert_library_track(&self, track: &LibraryTrack) -> BotResult<()> {
+        let query = sqlx::query!(
+            "INSERT INTO library_tracks (path, title, artist, album, duration_secs) VALUES ($1, $2, $3, $4, $5) ON CONFLICT (path) DO UPDATE SET title = $2, artist = $3, album = $4, duration_secs = $5;",
+            track.path,
+            track.title,
+            track.artist,
+            track.album,
+            track.duration.map(|duration| duration.as_secs() as i32)
+        );
+        query.execute(&self.pool).await?;
+        Ok(())
+    }
+
+    /// Remove all tracks whose path is not among the given ones
+    pub async fn prune_library_tracks(&self, paths: &[String]) -> BotResult<u64> {
+        let query = sqlx::query!(
+            "DELETE FROM library_tracks WHERE NOT path = ANY($1);",
+            paths
+        );
+        let result = query.execute(&self.pool).await?;
+        Ok(result.rows_affected())
+    }
+
+    /// Tracks whose tags or path contain the query, ignoring case
+    pub async fn search_library(&self, query: &str, limit: usize) -> BotResult<Vec<LibraryTrack>> {
+        let pattern = format!(
+            "%{}%",
+            query
+                .replace('\\', "\\\\")
+                .replace('%', "\\%")
+                .replace('_', "\\_")
+        );
+
+        let mut stream = sqlx::query!(
+            "SELECT * FROM library_tracks WHERE title ILIKE $1 OR artist ILIKE $1 OR album ILIKE $1 OR path ILIKE $1 ORDER BY artist, album, title LIMIT $2;",
+            pattern,
+            limit as i64
+        )
+        .fetch(&self.pool);
+
+        let mut tracks = Vec::new();
+        while let Some(entry) = stream.next().await.transpose()? {
+            tracks.push(LibraryTrack {
+                path: entry.path,
+                title: entry.title,
+                artist: entry.artist,
+                album: entry.album,
+                duration: entry
+                    .duration_secs
+                    .map(|secs| Duration::from_secs(secs.max(0) as u64)),
+            });
+        }
+        Ok(tracks)
+    }
+}
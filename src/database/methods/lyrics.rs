@@ -0,0 +1,28 @@
+use crate::{
+    database::{Database, Lyrics},
+    error::BotResult,
+};
+
+impl Database {
+    pub async fn get_lyrics(&self, query: &str) -> BotResult<Option<Lyrics>> {
+        let query = sqlx::query!("SELECT title, lyrics FROM lyrics WHERE query = $1;", query);
+
+        let lyrics = query.fetch_optional(&self.pool).await?.map(|entry| Lyrics {
+            title: entry.title,
+            text: entry.lyrics,
+        });
+
+        Ok(lyrics)
+    }
+
+    pub async fn insert_lyrics(&self, query: &str, lyrics: &Lyrics) -> BotResult<()> {
+        let query = sqlx::query!(
+            "INSERT INTO lyrics (query, title, lyrics) VALUES ($1, $2, $3) ON CONFLICT (query) DO UPDATE SET title = $2, lyrics = $3, cached_at = NOW();",
+            query,
+            lyrics.title,
+            lyrics.text
+        );
+        query.execute(&self.pool).await?;
+        Ok(())
+    }
+}
@@ -1,2 +1,9 @@
+mod buckets;
+mod library;
+mod lyrics;
 mod messages;
+mod music_history;
+mod music_queues;
+mod music_settings;
+mod playlists;
 mod unchecked_members;
@@ -0,0 +1,194 @@
+use std::time::Duration;
+
+use futures::StreamExt;
+use twilight_model::id::{GuildId, UserId};
+
+use crate::{
+    database::{Database, HistoryEntry, SongPlays},
+    error::BotResult,
+};
+
+impl Database {
+    /// Log that a song started playing, returns the id of the new entry
+    pub async fn insert_history_entry(
+        &self,
+        guild_id: GuildId,
+        url: &str,
+        title: Option<&str>,
+        artist: Option<&str>,
+        requester: Option<UserId>,
+    ) -> BotResult<i32> {
+        let query = sqlx::query!(
+            "INSERT INTO music_history (guild_id, url, title, artist, requester_id) VALUES ($1, $2, $3, $4, $5) RETURNING history_id;",
+            guild_id.0 as i64,
+            url,
+            title,
+            artist,
+            requester.map(|id| id.0 as i64)
+        );
+        let row = query.fetch_one(&self.pool).await?;
+        Ok(row.history_id)
+    }
+
+    pub async fn update_history_listened(&self, id: i32, listened: Duration) -> BotResult<()> {
+        let query = sqlx::query!(
+            "UPDATE music_history SET listened_ms = $2 WHERE history_id = $1;",
+            id,
+            listened.as_millis() as i64
+        );
+        query.execute(&self.pool).await?;
+        Ok(())
+    }
+
+    pub async fn get_history_entry(
+        &self,
+        guild_id: GuildId,
+        id: i32,
+    ) -> BotResult<Option<HistoryEntry>> {
+        let query = sqlx::query!(
+            "SELECT * FROM music_history WHERE guild_id = $1 AND history_id = $2;",
+            guild_id.0 as i64,
+            id
+        );
+
+        let entry = query
+            .fetch_optional(&self.pool)
+            .await?
+            .map(|entry| HistoryEntry {
+                id: entry.history_id,
+                url: entry.url,
+                title: entry.title,
+                requester: entry.requester_id.map(|id| UserId(id as u64)),
+                listened: Duration::from_millis(entry.listened_ms.max(0) as u64),
+                played_at: entry.played_at,
+            });
+
+        Ok(entry)
+    }
+
+    /// Latest entries of the guild, newest first
+    pub async fn get_recent_history(
+        &self,
+        guild_id: GuildId,
+        limit: usize,
+    ) -> BotResult<Vec<HistoryEntry>> {
+        let mut stream = sqlx::query!(
+            "SELECT * FROM music_history WHERE guild_id = $1 ORDER BY played_at DESC LIMIT $2;",
+            guild_id.0 as i64,
+            limit as i64
+        )
+        .fetch(&self.pool);
+
+        let mut entries = Vec::new();
+        while let Some(entry) = stream.next().await.transpose()? {
+            entries.push(HistoryEntry {
+                id: entry.history_id,
+                url: entry.url,
+                title: entry.title,
+                requester: entry.requester_id.map(|id| UserId(id as u64)),
+                listened: Duration::from_millis(entry.listened_ms.max(0) as u64),
+                played_at: entry.played_at,
+            });
+        }
+        Ok(entries)
+    }
+
+    /// Most played songs of the guild
+    pub async fn get_top_songs(
+        &self,
+        guild_id: GuildId,
+        limit: usize,
+    ) -> BotResult<Vec<SongPlays>> {
+        let mut stream = sqlx::query!(
+            r#"SELECT url, MAX(title) AS title, COUNT(*) AS "plays!" FROM music_history WHERE guild_id = $1 GROUP BY url ORDER BY 3 DESC LIMIT $2;"#,
+            guild_id.0 as i64,
+            limit as i64
+        )
+        .fetch(&self.pool);
+
+        let mut songs = Vec::new();
+        while let Some(entry) = stream.next().await.transpose()? {
+            songs.push(SongPlays {
+                url: entry.url,
+                title: entry.title,
+                plays: entry.plays,
+            });
+        }
+        Ok(songs)
+    }
+
+    /// Members of the guild whose songs were played the most, paired with their amount of plays
+    pub async fn get_top_requesters(
+        &self,
+        guild_id: GuildId,
+        limit: usize,
+    ) -> BotResult<Vec<(UserId, i64)>> {
+        let mut stream = sqlx::query!(
+            r#"SELECT requester_id AS "requester_id!", COUNT(*) AS "plays!" FROM music_history WHERE guild_id = $1 AND requester_id IS NOT NULL GROUP BY requester_id ORDER BY 2 DESC LIMIT $2;"#,
+            guild_id.0 as i64,
+            limit as i64
+        )
+        .fetch(&self.pool);
+
+        let mut requesters = Vec::new();
+        while let Some(entry) = stream.next().await.transpose()? {
+            requesters.push((UserId(entry.requester_id as u64), entry.plays));
+        }
+        Ok(requesters)
+    }
+
+    /// Songs that were played right after the given one, most frequent first
+    pub async fn get_coplayed_songs(
+        &self,
+        guild_id: GuildId,
+        url: &str,
+        limit: usize,
+    ) -> BotResult<Vec<String>> {
+        let mut stream = sqlx::query!(
+            r#"SELECT next_url AS "url!" FROM (SELECT url, LEAD(url) OVER (ORDER BY played_at) AS next_url FROM music_history WHERE guild_id = $1) AS pairs WHERE url = $2 AND next_url IS NOT NULL AND next_url <> $2 GROUP BY next_url ORDER BY COUNT(*) DESC LIMIT $3;"#,
+            guild_id.0 as i64,
+            url,
+            limit as i64
+        )
+        .fetch(&self.pool);
+
+        let mut urls = Vec::new();
+        while let Some(entry) = stream.next().await.transpose()? {
+            urls.push(entry.url);
+        }
+        Ok(urls)
+    }
+
+    /// Other songs of the artist, most played first
+    pub async fn get_artist_songs(
+        &self,
+        guild_id: GuildId,
+        artist: &str,
+        url: &str,
+        limit: usize,
+    ) -> BotResult<Vec<String>> {
+        let mut stream = sqlx::query!(
+            "SELECT url FROM music_history WHERE guild_id = $1 AND LOWER(artist) = LOWER($2) AND url <> $3 GROUP BY url ORDER BY COUNT(*) DESC LIMIT $4;",
+            guild_id.0 as i64,
+            artist,
+            url,
+            limit as i64
+        )
+        .fetch(&self.pool);
+
+        let mut urls = Vec::new();
+        while let Some(entry) = stream.next().await.transpose()? {
+            urls.push(entry.url);
+        }
+        Ok(urls)
+    }
+
+    pub async fn get_total_listened(&self, guild_id: GuildId) -> BotResult<Duration> {
+        let query = sqlx::query!(
+            r#"SELECT COALESCE(SUM(listened_ms), 0)::INT8 AS "listened_ms!" FROM music_history WHERE guild_id = $1;"#,
+            guild_id.0 as i64
+        );
+        let row = query.fetch_one(&self.pool).await?;
+        Ok(Duration::from_millis(row.listened_ms.max(0) as u64))
+    }
+}
@@ -0,0 +1,72 @@
+use std::time::Duration;
+
+use futures::StreamExt;
+use twilight_model::id::{GuildId, UserId};
+
+use crate::{
+    database::{Database, QueuedTrack},
+    error::BotResult,
+};
+
+impl Database {
+    pub async fn get_music_queue(&self, guild_id: GuildId) -> BotResult<Vec<QueuedTrack>> {
+        let mut stream = sqlx::query!(
+            "SELECT * FROM music_queues WHERE guild_id = $1 ORDER BY position;",
+            guild_id.0 as i64
+        )
+        .fetch(&self.pool);
+
+        let mut tracks = Vec::new();
+        while let Some(entry) = stream.next().await.transpose()? {
+            tracks.push(QueuedTrack {
+                url: entry.url,
+                title: entry.title,
+                requester: entry.requester_id.map(|id| UserId(id as u64)),
+                offset: Duration::from_millis(entry.offset_ms.max(0) as u64),
+            });
+        }
+        Ok(tracks)
+    }
+
+    /// Replaces the saved queue of the guild
+    pub async fn save_music_queue(
+        &self,
+        guild_id: GuildId,
+        tracks: &[QueuedTrack],
+    ) -> BotResult<()> {
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query!(
+            "DELETE FROM music_queues WHERE guild_id = $1;",
+            guild_id.0 as i64
+        )
+        .execute(&mut tx)
+        .await?;
+
+        for (track, position) in tracks.iter().zip(0..) {
+            sqlx::query!(
+                "INSERT INTO music_queues (guild_id, position, url, title, requester_id, offset_ms) VALUES ($1, $2, $3, $4, $5, $6);",
+                guild_id.0 as i64,
+                position as i32,
+                track.url,
+                track.title,
+                track.requester.map(|id| id.0 as i64),
+                track.offset.as_millis() as i64
+            )
+            .execute(&mut tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    pub async fn remove_music_queue(&self, guild_id: GuildId) -> BotResult<bool> {
+        let query = sqlx::query!(
+            "DELETE FROM music_queues WHERE guild_id = $1;",
+            guild_id.0 as i64
+        );
+        let result = query.execute(&self.pool).await?;
+        Ok(result.rows_affected() > 0)
+    }
+}
@@ -0,0 +1,51 @@
+use futures::StreamExt;
+use hashbrown::HashMap;
+use twilight_model::id::GuildId;
+
+use crate::{
+    database::{Database, MusicSettings},
+    error::BotResult,
+};
+
+impl Database {
+    pub async fn get_music_settings(&self) -> BotResult<HashMap<GuildId, MusicSettings>> {
+        let mut stream = sqlx::query!("SELECT * FROM music_settings;").fetch(&self.pool);
+        let mut settings = HashMap::new();
+        while let Some(entry) = stream.next().await.transpose()? {
+            let guild_settings = MusicSettings {
+                vote_skip_percent: entry.vote_skip_percent.clamp(0, 100) as u8,
+                playlist_limit: entry.playlist_limit.max(1) as u16,
+                idle_timeout_mins: entry.idle_timeout_mins.max(0) as u16,
+                autoplay: entry.autoplay,
+                autoplay_playlist: entry.autoplay_playlist,
+                autoplay_no_repeat: entry.autoplay_no_repeat.max(0) as u16,
+                volume_percent: entry.volume_percent.max(0) as u16,
+                normalize: entry.normalize,
+            };
+
+            settings.insert(GuildId(entry.guild_id as u64), guild_settings);
+        }
+        Ok(settings)
+    }
+
+    pub async fn upsert_music_settings(
+        &self,
+        guild_id: GuildId,
+        settings: &MusicSettings,
+    ) -> BotResult<()> {
+        let query = sqlx::query!(
+            "INSERT INTO music_settings (guild_id, vote_skip_percent, playlist_limit, idle_timeout_mins, autoplay, autoplay_playlist, autoplay_no_repeat, volume_percent, normalize) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9) ON CONFLICT (guild_id) DO UPDATE SET vote_skip_percent = $2, playlist_limit = $3, idle_timeout_mins = $4, autoplay = $5, autoplay_playlist = $6, autoplay_no_repeat = $7, volume_percent = $8, normalize = $9;",
+            guild_id.0 as i64,
+            settings.vote_skip_percent as i16,
+            settings.playlist_limit as i16,
+            settings.idle_timeout_mins as i16,
+            settings.autoplay,
+            settings.autoplay_playlist,
+            settings.autoplay_no_repeat as i16,
+            settings.volume_percent as i16,
+            settings.normalize
+        );
+        query.execute(&self.pool).await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,163 @@
+use std::time::Duration;
+
+use futures::StreamExt;
+use twilight_model::id::{GuildId, UserId};
+
+use crate::{
+    database::{Database, Playlist, PlaylistOwner, PlaylistTrack},
+    error::BotResult,
+};
+
+impl Database {
+    pub async fn get_playlist(
+        &self,
+        owner: PlaylistOwner,
+        name: &str,
+    ) -> BotResult<Option<Playlist>> {
+        let (owner_id, is_guild) = owner.key();
+
+        let query = sqlx::query!(
+            "SELECT playlist_id, name, (SELECT COUNT(*) FROM playlist_tracks t WHERE t.playlist_id = p.playlist_id) AS len FROM playlists p WHERE owner_id = $1 AND is_guild = $2 AND name = $3;",
+            owner_id,
+            is_guild,
+            name
+        );
+
+        let playlist = query
+            .fetch_optional(&self.pool)
+            .await?
+            .map(|entry| Playlist {
+                id: entry.playlist_id,
+                name: entry.name,
+                owner,
+                len: entry.len.unwrap_or(0).max(0) as usize,
+            });
+
+        Ok(playlist)
+    }
+
+    /// All playlists of the user and, if specified, of the guild
+    pub async fn get_playlists(
+        &self,
+        user_id: UserId,
+        guild_id: Option<GuildId>,
+    ) -> BotResult<Vec<Playlist>> {
+        let mut stream = sqlx::query!(
+            "SELECT playlist_id, owner_id, is_guild, name, (SELECT COUNT(*) FROM playlist_tracks t WHERE t.playlist_id = p.playlist_id) AS len FROM playlists p WHERE (owner_id = $1 AND NOT is_guild) OR (owner_id = $2 AND is_guild) ORDER BY is_guild, name;",
+            user_id.0 as i64,
+            guild_id.map(|id| id.0 as i64)
+        )
+        .fetch(&self.pool);
+
+        let mut playlists = Vec::new();
+        while let Some(entry) = stream.next().await.transpose()? {
+            let owner = if entry.is_guild {
+                PlaylistOwner::Guild(GuildId(entry.owner_id as u64))
+            } else {
+                PlaylistOwner::User(UserId(entry.owner_id as u64))
+            };
+
+            playlists.push(Playlist {
+                id: entry.playlist_id,
+                name: entry.name,
+                owner,
+                len: entry.len.unwrap_or(0).max(0) as usize,
+            });
+        }
+        Ok(playlists)
+    }
+
+    /// Returns `false` if the owner already has a playlist with that name
+    pub async fn create_playlist(
+        &self,
+        owner: PlaylistOwner,
+        name: &str,
+        tracks: &[PlaylistTrack],
+    ) -> BotResult<bool> {
+        let (owner_id, is_guild) = owner.key();
+        let mut tx = self.pool.begin().await?;
+
+        let created = sqlx::query!(
+            "INSERT INTO playlists (owner_id, is_guild, name) VALUES ($1, $2, $3) ON CONFLICT (owner_id, is_guild, name) DO NOTHING RETURNING playlist_id;",
+            owner_id,
+            is_guild,
+            name
+        )
+        .fetch_optional(&mut tx)
+        .await?;
+
+        let playlist_id = match created {
+            Some(entry) => entry.playlist_id,
+            None => return Ok(false),
+        };
+
+        for (track, position) in tracks.iter().zip(0..) {
+            sqlx::query!(
+                "INSERT INTO playlist_tracks (playlist_id, position, url, title, duration_secs) VALUES ($1, $2, $3, $4, $5);",
+                playlist_id,
+                position as i32,
+                track.url,
+                track.title,
+                track.duration.map(|duration| duration.as_secs() as i32)
+            )
+            .execute(&mut tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+        Ok(true)
+    }
+
+    pub async fn delete_playlist(&self, playlist_id: i32) -> BotResult<()> {
+        let query = sqlx::query!("DELETE FROM playlists WHERE playlist_id = $1;", playlist_id);
+        query.execute(&self.pool).await?;
+        Ok(())
+    }
+
+    pub async fn get_playlist_tracks(&self, playlist_id: i32) -> BotResult<Vec<PlaylistTrack>> {
+        let mut stream = sqlx::query!(
+            "SELECT * FROM playlist_tracks WHERE playlist_id = $1 ORDER BY position;",
+            playlist_id
+        )
+        .fetch(&self.pool);
+
+        let mut tracks = Vec::new();
+        while let Some(entry) = stream.next().await.transpose()? {
+            tracks.push(PlaylistTrack {
+                url: entry.url,
+                title: entry.title,
+                duration: entry
+                    .duration_secs
+                    .map(|secs| Duration::from_secs(secs.max(0) as u64)),
+            });
+        }
+        Ok(tracks)
+    }
+
+    pub async fn add_playlist_track(
+        &self,
+        playlist_id: i32,
+        track: &PlaylistTrack,
+    ) -> BotResult<()> {
+        let query = sqlx::query!(
+            "INSERT INTO playlist_tracks (playlist_id, position, url, title, duration_secs) VALUES ($1, (SELECT COALESCE(MAX(position) + 1, 0) FROM playlist_tracks WHERE playlist_id = $1), $2, $3, $4);",
+            playlist_id,
+            track.url,
+            track.title,
+            track.duration.map(|duration| duration.as_secs() as i32)
+        );
+        query.execute(&self.pool).await?;
+        Ok(())
+    }
+
+    /// Remove the track at the zero-based index, returns `false` if there is none
+    pub async fn remove_playlist_track(&self, playlist_id: i32, index: usize) -> BotResult<bool> {
+        let query = sqlx::query!(
+            "DELETE FROM playlist_tracks WHERE playlist_id = $1 AND position = (SELECT position FROM playlist_tracks WHERE playlist_id = $1 ORDER BY position LIMIT 1 OFFSET $2);",
+            playlist_id,
+            index as i64
+        );
+        let result = query.execute(&self.pool).await?;
+        Ok(result.rows_affected() > 0)
+    }
+}
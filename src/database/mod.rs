@@ -1,6 +1,11 @@
 mod methods;
 mod models;
 
+pub use models::{
+    BucketOverride, HistoryEntry, LibraryTrack, Lyrics, MusicSettings, Playlist, PlaylistOwner,
+    PlaylistTrack, QueuedTrack, SongPlays,
+};
+
 use sqlx::{postgres::PgPoolOptions, PgPool};
 
 use crate::error::BotResult;
@@ -0,0 +1,10 @@
+use twilight_model::id::GuildId;
+
+use crate::buckets::{BucketScope, Ratelimit};
+
+pub struct BucketOverride {
+    pub guild_id: GuildId,
+    pub bucket: String,
+    pub scope: BucketScope,
+    pub ratelimit: Ratelimit,
+}
@@ -0,0 +1,21 @@
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use twilight_model::id::UserId;
+
+/// Song that started playing in a guild
+pub struct HistoryEntry {
+    pub id: i32,
+    pub url: String,
+    pub title: Option<String>,
+    pub requester: Option<UserId>,
+    pub listened: Duration,
+    pub played_at: DateTime<Utc>,
+}
+
+/// How often a song was played in a guild
+pub struct SongPlays {
+    pub url: String,
+    pub title: Option<String>,
+    pub plays: i64,
+}
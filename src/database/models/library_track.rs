@@ -0,0 +1,24 @@
+use std::{path::Path, time::Duration};
+
+/// Audio file of the local music library
+pub struct LibraryTrack {
+    pub path: String,
+    pub title: Option<String>,
+    pub artist: Option<String>,
+    pub album: Option<String>,
+    pub duration: Option<Duration>,
+}
+
+impl LibraryTrack {
+    /// Artist and title if tagged, the file name otherwise
+    pub fn name(&self) -> String {
+        match (&self.artist, &self.title) {
+            (Some(artist), Some(title)) => format!("{} - {}", artist, title),
+            (None, Some(title)) => title.clone(),
+            _ => Path::new(&self.path).file_stem().map_or_else(
+                || self.path.clone(),
+                |stem| stem.to_string_lossy().into_owned(),
+            ),
+        }
+    }
+}
@@ -0,0 +1,5 @@
+/// Lyrics of a song as found by a lyrics provider
+pub struct Lyrics {
+    pub title: String,
+    pub text: String,
+}
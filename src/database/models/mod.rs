@@ -0,0 +1,15 @@
+mod bucket_override;
+mod history_entry;
+mod library_track;
+mod lyrics;
+mod music_settings;
+mod playlist;
+mod queued_track;
+
+pub use bucket_override::BucketOverride;
+pub use history_entry::{HistoryEntry, SongPlays};
+pub use library_track::LibraryTrack;
+pub use lyrics::Lyrics;
+pub use music_settings::MusicSettings;
+pub use playlist::{Playlist, PlaylistOwner, PlaylistTrack};
+pub use queued_track::QueuedTrack;
@@ -0,0 +1,35 @@
+/// Per-guild configuration of music commands
+#[derive(Clone)]
+pub struct MusicSettings {
+    /// Percentage of listeners that need to vote to skip a song
+    pub vote_skip_percent: u8,
+    /// Maximum amount of songs added from a single playlist
+    pub playlist_limit: u16,
+    /// Minutes without music or listeners until the bot leaves, 0 to never leave
+    pub idle_timeout_mins: u16,
+    /// Whether related songs are added once the queue runs out
+    pub autoplay: bool,
+    /// Server playlist that autoplay shuffles from if the history has nothing related
+    pub autoplay_playlist: Option<String>,
+    /// Amount of latest songs that autoplay won't pick again
+    pub autoplay_no_repeat: u16,
+    /// Volume that new songs start at
+    pub volume_percent: u16,
+    /// Whether the volume of new songs is adjusted by their loudness
+    pub normalize: bool,
+}
+
+impl Default for MusicSettings {
+    fn default() -> Self {
+        Self {
+            vote_skip_percent: 50,
+            playlist_limit: 100,
+            idle_timeout_mins: 5,
+            autoplay: false,
+            autoplay_playlist: None,
+            autoplay_no_repeat: 20,
+            volume_percent: 100,
+            normalize: false,
+        }
+    }
+}
@@ -0,0 +1,33 @@
+use std::time::Duration;
+
+use twilight_model::id::{GuildId, UserId};
+
+/// Whoever a saved playlist belongs to
+#[derive(Copy, Clone)]
+pub enum PlaylistOwner {
+    User(UserId),
+    Guild(GuildId),
+}
+
+impl PlaylistOwner {
+    pub(in crate::database) fn key(self) -> (i64, bool) {
+        match self {
+            Self::User(user_id) => (user_id.0 as i64, false),
+            Self::Guild(guild_id) => (guild_id.0 as i64, true),
+        }
+    }
+}
+
+pub struct Playlist {
+    pub id: i32,
+    pub name: String,
+    pub owner: PlaylistOwner,
+    pub len: usize,
+}
+
+/// Song of a saved playlist with the metadata it had when it was added
+pub struct PlaylistTrack {
+    pub url: String,
+    pub title: Option<String>,
+    pub duration: Option<Duration>,
+}
@@ -0,0 +1,11 @@
+use std::time::Duration;
+
+use twilight_model::id::UserId;
+
+/// Song of a music queue that was saved to be restored later on
+pub struct QueuedTrack {
+    pub url: String,
+    pub title: Option<String>,
+    pub requester: Option<UserId>,
+    pub offset: Duration,
+}
@@ -14,17 +14,24 @@ use std::fmt;
 use std::io::Error as IoError;
 use std::num::ParseFloatError;
 use twilight_gateway::cluster::{ClusterCommandError, ClusterStartError};
+use twilight_http::request::application::interaction::create_followup_message::CreateFollowupMessageError;
 use twilight_http::request::application::interaction::update_original_response::UpdateOriginalResponseError;
 use twilight_http::request::application::InteractionError;
+use twilight_http::request::channel::message::update_message::UpdateMessageError;
 use twilight_http::request::prelude::create_message::CreateMessageError;
 use twilight_http::response::DeserializeBodyError;
 use twilight_http::Error as TwilightHttpError;
 use twilight_model::application::interaction::ApplicationCommand;
+use twilight_model::gateway::payload::update_presence::UpdatePresenceError;
+
+use crate::utils::{GENERAL_ISSUE, OSU_API_ISSUE};
 
 pub type BotResult<T> = Result<T, Error>;
 
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
+    #[error("Invalid command arguments.")]
+    Args(#[from] ArgsError),
     #[error("Error occurred on cluster request.")]
     ClusterCommand(#[from] ClusterCommandError),
     #[error("Failed to start cluster.")]
@@ -35,10 +42,16 @@ pub enum Error {
         #[source]
         src: Box<Error>,
     },
+    #[error("Failed to create followup message.")]
+    CreateFollowupMessage(#[from] CreateFollowupMessageError),
     #[error("Failed to create message.")]
     CreateMessage(#[from] CreateMessageError),
     #[error("Failed to deserialize Discord object.")]
     DeserializeBody(#[from] DeserializeBodyError),
+    #[error("ffmpeg failed: {0}")]
+    Ffmpeg(String),
+    #[error("ffprobe failed: {0}")]
+    Ffprobe(String),
     #[error("Failed to interact with Discord.")]
     Interaction(#[from] InteractionError),
     #[error("I/O error.")]
@@ -53,6 +66,10 @@ pub enum Error {
     MapDownload(#[from] MapDownloadError),
     #[error("Failed to migrate database.")]
     Migration(#[from] MigrateError),
+    #[error("Missing environment variable ({0}).")]
+    MissingEnv(&'static str),
+    #[error("Command requires a guild but was used elsewhere.")]
+    MissingGuild,
     #[error("Slash author was not found.")]
     MissingSlashAuthor,
     #[error("Failed to communicate with osu! API.")]
@@ -61,6 +78,8 @@ pub enum Error {
     ParseFloat(#[from] ParseFloatError),
     #[error("Failed to parse timestamp with chrono.")]
     ParseTime(#[from] ParseError),
+    #[error("Failed to create presence update.")]
+    Presence(#[from] UpdatePresenceError),
     #[error("Regex error")]
     Regex(#[from] RegexError),
     #[error("Reqwest error.")]
@@ -75,8 +94,46 @@ pub enum Error {
     TwilightHttp(#[from] TwilightHttpError),
     #[error("Received unknown interaction ({}): {command:#?}", .command.data.name)]
     UnknownInteraction { command: Box<ApplicationCommand> },
+    #[error("Error while updating message.")]
+    UpdateMessage(#[from] UpdateMessageError),
     #[error("Error while updating original response.")]
     UpdateOriginalResponse(#[from] UpdateOriginalResponseError),
+    #[error("youtube-dl failed: {0}")]
+    Ytdl(String),
+}
+
+impl Error {
+    /// Message to show the author of a command that failed with this error
+    pub fn user_message(&self) -> &'static str {
+        match self {
+            Self::Command { src, .. } => src.user_message(),
+            Self::Args(_) => "Invalid command options, please try again",
+            Self::JoinVoicechat(_) => "Failed to join the voice channel, am I allowed to?",
+            Self::Osu(_) => OSU_API_ISSUE,
+            _ => GENERAL_ISSUE,
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ArgsError {
+    #[error("Option `{name}` has an unexpected type.")]
+    InvalidType { name: &'static str },
+    #[error("`{value}` is not a valid choice for option `{name}`.")]
+    InvalidChoice { name: &'static str, value: String },
+    #[error("Missing required option `{name}`.")]
+    Missing { name: &'static str },
+    #[error("Value `{value}` of option `{name}` is out of range (min: {min:?}, max: {max:?}).")]
+    OutOfRange {
+        name: &'static str,
+        value: String,
+        min: Option<i64>,
+        max: Option<i64>,
+    },
+    #[error("Failed to parse `{value}` for option `{name}`.")]
+    Parse { name: &'static str, value: String },
+    #[error("Option `{name}` is missing from the resolved interaction data.")]
+    Unresolved { name: &'static str },
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -0,0 +1,142 @@
+use std::{
+    collections::HashMap,
+    path::{Component, Path, PathBuf},
+    time::Duration,
+};
+
+use serde::Deserialize;
+use tokio::{fs, process::Command};
+
+use crate::{
+    database::{Database, LibraryTrack},
+    error::{BotResult, Error},
+};
+
+/// File types that are picked up by the library
+pub const AUDIO_EXTENSIONS: [&str; 4] = ["mp3", "ogg", "wav", "flac"];
+
+/// Directory of audio files on the bot's machine
+pub struct MusicLibrary {
+    root: PathBuf,
+}
+
+#[derive(Deserialize)]
+struct ProbeOutput {
+    format: ProbeFormat,
+}
+
+#[derive(Deserialize)]
+struct ProbeFormat {
+    duration: Option<String>,
+    #[serde(default)]
+    tags: HashMap<String, String>,
+}
+
+impl MusicLibrary {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// Whether the path points into the library, only those files may be played
+    pub fn contains(&self, path: &str) -> bool {
+        let path = Path::new(path);
+
+        path.starts_with(&self.root)
+            && path
+                .components()
+                .all(|component| component != Component::ParentDir)
+    }
+
+    /// Store all audio files with their tags in the database and forget about removed ones.
+    /// Returns the amount of indexed files.
+    pub async fn index(&self, database: &Database) -> BotResult<usize> {
+        let files = self.audio_files().await?;
+        let mut paths = Vec::with_capacity(files.len());
+
+        for file in files {
+            let track = match probe(&file).await {
+                Ok(track) => track,
+                Err(why) => {
+                    unwind_error!(warn, why, "Failed to index {}: {}", file.display());
+                    continue;
+                }
+            };
+
+            database.upsert_library_track(&track).await?;
+            paths.push(track.path);
+        }
+
+        database.prune_library_tracks(&paths).await?;
+
+        Ok(paths.len())
+    }
+
+    async fn audio_files(&self) -> BotResult<Vec<PathBuf>> {
+        let mut dirs = vec![self.root.clone()];
+        let mut files = Vec::new();
+
+        while let Some(dir) = dirs.pop() {
+            let mut entries = fs::read_dir(&dir).await?;
+
+            while let Some(entry) = entries.next_entry().await? {
+                let path = entry.path();
+
+                if entry.file_type().await?.is_dir() {
+                    dirs.push(path);
+                } else if is_audio_file(&path) {
+                    files.push(path);
+                }
+            }
+        }
+
+        Ok(files)
+    }
+}
+
+fn is_audio_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .map_or(false, |ext| {
+            AUDIO_EXTENSIONS
+                .iter()
+                .any(|audio| ext.eq_ignore_ascii_case(audio))
+        })
+}
+
+/// Read the tags and duration of the file through ffprobe
+async fn probe(path: &Path) -> BotResult<LibraryTrack> {
+    let output = Command::new("ffprobe")
+        .args(&["-v", "quiet", "-print_format", "json", "-show_format"])
+        .arg(path)
+        .output()
+        .await?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+
+        return Err(Error::Ffprobe(stderr.trim().to_owned()));
+    }
+
+    let ProbeOutput { format } = serde_json::from_slice(&output.stdout)?;
+
+    // Tag names differ in case between formats
+    let tags: HashMap<_, _> = format
+        .tags
+        .into_iter()
+        .map(|(key, value)| (key.to_lowercase(), value))
+        .collect();
+
+    let duration = format
+        .duration
+        .and_then(|secs| secs.parse::<f64>().ok())
+        .filter(|secs| secs.is_finite() && *secs >= 0.0)
+        .map(Duration::from_secs_f64);
+
+    Ok(LibraryTrack {
+        path: path.to_string_lossy().into_owned(),
+        title: tags.get("title").cloned(),
+        artist: tags.get("artist").cloned(),
+        album: tags.get("album").cloned(),
+        duration,
+    })
+}
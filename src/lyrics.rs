@@ -0,0 +1,265 @@
+use std::{fmt, path::PathBuf, time::Duration};
+
+use reqwest::{Client, StatusCode, Url};
+use serde::Deserialize;
+use tokio::fs;
+
+use crate::{database::Lyrics, error::BotResult, utils::LYRICS_OVH_API};
+
+/// Lookups shouldn't keep the command waiting for too long
+const OVH_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Song to look up lyrics for
+pub struct LyricsQuery {
+    pub artist: Option<String>,
+    pub title: String,
+}
+
+impl LyricsQuery {
+    /// Parse queries like `artist - title` or just `title`.
+    /// Parts in brackets, e.g. `(Official Video)` of youtube titles, are ignored.
+    pub fn parse(query: &str) -> Self {
+        let query = strip_brackets(query);
+
+        match query.split_once(" - ") {
+            Some((artist, title)) => Self {
+                artist: Some(artist.trim().to_owned()),
+                title: title.trim().to_owned(),
+            },
+            None => Self {
+                artist: None,
+                title: query,
+            },
+        }
+    }
+
+    /// Normalized form of the query, used to cache lyrics
+    pub fn key(&self) -> String {
+        self.to_string().to_lowercase()
+    }
+}
+
+impl fmt::Display for LyricsQuery {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.artist {
+            Some(ref artist) => write!(f, "{} - {}", artist, self.title),
+            None => f.write_str(&self.title),
+        }
+    }
+}
+
+fn strip_brackets(s: &str) -> String {
+    let mut depth = 0_usize;
+    let mut stripped = String::with_capacity(s.len());
+
+    for c in s.chars() {
+        match c {
+            '(' | '[' => depth += 1,
+            ')' | ']' => depth = depth.saturating_sub(1),
+            _ if depth == 0 => stripped.push(c),
+            _ => {}
+        }
+    }
+
+    stripped.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Source of song lyrics
+#[async_trait]
+pub trait LyricsProvider: Send + Sync {
+    /// Lyrics of the song, `None` if the provider doesn't know it
+    async fn lyrics(&self, query: &LyricsQuery) -> BotResult<Option<Lyrics>>;
+}
+
+/// Lyrics from the public lyrics.ovh API
+pub struct LyricsOvh {
+    client: Client,
+}
+
+#[derive(Deserialize)]
+struct OvhLyrics {
+    lyrics: String,
+}
+
+#[derive(Deserialize)]
+struct OvhSuggestions {
+    data: Vec<OvhSuggestion>,
+}
+
+#[derive(Deserialize)]
+struct OvhSuggestion {
+    title: String,
+    artist: OvhArtist,
+}
+
+#[derive(Deserialize)]
+struct OvhArtist {
+    name: String,
+}
+
+impl LyricsOvh {
+    pub fn new(client: Client) -> Self {
+        Self { client }
+    }
+
+    fn endpoint(segments: &[&str]) -> Url {
+        let mut url = Url::parse(LYRICS_OVH_API).expect("invalid lyrics.ovh url");
+
+        url.path_segments_mut()
+            .expect("lyrics.ovh url can't have a path")
+            .extend(segments);
+
+        url
+    }
+
+    /// Artist and title of the best match for a search term
+    async fn suggest(&self, term: &str) -> BotResult<Option<(String, String)>> {
+        let url = Self::endpoint(&["suggest", term]);
+        let bytes = self
+            .client
+            .get(url)
+            .timeout(OVH_TIMEOUT)
+            .send()
+            .await?
+            .bytes()
+            .await?;
+        let suggestions: OvhSuggestions = serde_json::from_slice(&bytes)?;
+
+        let suggestion = suggestions
+            .data
+            .into_iter()
+            .next()
+            .map(|suggestion| (suggestion.artist.name, suggestion.title));
+
+        Ok(suggestion)
+    }
+}
+
+#[async_trait]
+impl LyricsProvider for LyricsOvh {
+    async fn lyrics(&self, query: &LyricsQuery) -> BotResult<Option<Lyrics>> {
+        // The API only finds lyrics by artist and title
+        let (artist, title) = match query.artist {
+            Some(ref artist) => (artist.clone(), query.title.clone()),
+            None => match self.suggest(&query.title).await? {
+                Some(song) => song,
+                None => return Ok(None),
+            },
+        };
+
+        let url = Self::endpoint(&["v1", &artist, &title]);
+        let response = self.client.get(url).timeout(OVH_TIMEOUT).send().await?;
+
+        if response.status() == StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+
+        let bytes = response.error_for_status()?.bytes().await?;
+        let OvhLyrics { lyrics } = serde_json::from_slice(&bytes)?;
+        let text = lyrics.replace("\r\n", "\n").trim().to_owned();
+
+        if text.is_empty() {
+            return Ok(None);
+        }
+
+        let lyrics = Lyrics {
+            title: format!("{} - {}", artist, title),
+            text,
+        };
+
+        Ok(Some(lyrics))
+    }
+}
+
+/// Lyrics stored as `<artist> - <title>.txt` or `<title>.txt` files in a directory
+pub struct LyricsDirectory {
+    path: PathBuf,
+}
+
+impl LyricsDirectory {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+#[async_trait]
+impl LyricsProvider for LyricsDirectory {
+    async fn lyrics(&self, query: &LyricsQuery) -> BotResult<Option<Lyrics>> {
+        let mut entries = fs::read_dir(&self.path).await?;
+
+        while let Some(entry) = entries.next_entry().await? {
+            let path = entry.path();
+
+            if path.extension().map_or(true, |ext| ext != "txt") {
+                continue;
+            }
+
+            let name = match path.file_stem().and_then(|stem| stem.to_str()) {
+                Some(name) => name,
+                None => continue,
+            };
+
+            let file = LyricsQuery::parse(name);
+            let same_title = file.title.eq_ignore_ascii_case(&query.title);
+
+            // Files or queries without artist match any artist
+            let same_artist = match (&file.artist, &query.artist) {
+                (Some(a), Some(b)) => a.eq_ignore_ascii_case(b),
+                _ => true,
+            };
+
+            if same_title && same_artist {
+                let lyrics = Lyrics {
+                    title: name.to_owned(),
+                    text: fs::read_to_string(&path).await?.trim().to_owned(),
+                };
+
+                return Ok(Some(lyrics));
+            }
+        }
+
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_query() {
+        let query = LyricsQuery::parse("Artist - Song (Official Video) [HD]");
+        assert_eq!(query.artist.as_deref(), Some("Artist"));
+        assert_eq!(query.title, "Song");
+        assert_eq!(query.key(), "artist - song");
+
+        let query = LyricsQuery::parse("  Just a  song ");
+        assert_eq!(query.artist, None);
+        assert_eq!(query.title, "Just a song");
+    }
+
+    #[tokio::test]
+    async fn test_lyrics_directory() {
+        let dir = std::env::temp_dir().join(format!("fivebot-lyrics-{}", std::process::id()));
+        fs::create_dir_all(&dir).await.unwrap();
+        fs::write(dir.join("Artist - Song.txt"), "la la la\n")
+            .await
+            .unwrap();
+
+        let provider = LyricsDirectory::new(&dir);
+
+        let found = provider.lyrics(&LyricsQuery::parse("artist - song")).await;
+        assert_eq!(
+            found.unwrap().map(|lyrics| lyrics.text).as_deref(),
+            Some("la la la")
+        );
+
+        let found = provider.lyrics(&LyricsQuery::parse("Song (Lyrics)")).await;
+        assert!(found.unwrap().is_some());
+
+        let missing = provider.lyrics(&LyricsQuery::parse("Other - Song")).await;
+        assert!(missing.unwrap().is_none());
+
+        fs::remove_dir_all(dir).await.unwrap();
+    }
+}
@@ -14,17 +14,22 @@ macro_rules! unwind_error {
     };
 }
 
+mod buckets;
 mod commands;
 mod context;
 mod database;
 mod error;
+mod library;
 mod logging;
+mod lyrics;
 mod osu_irc;
+mod pagination;
 mod stats;
 mod utils;
 
+use buckets::Buckets;
 use context::Context;
-use dashmap::DashSet;
+use dashmap::{DashMap, DashSet};
 use database::Database;
 use error::{BotResult, Error};
 
@@ -43,11 +48,14 @@ use twilight_http::Client as HttpClient;
 use twilight_model::{
     application::interaction::Interaction,
     gateway::presence::{ActivityType, Status},
-    id::GuildId,
+    id::{ChannelId, GuildId},
 };
 use twilight_standby::Standby;
 
-use crate::commands::handle_interaction;
+use crate::commands::{handle_component, handle_interaction};
+use crate::library::MusicLibrary;
+use crate::lyrics::{LyricsDirectory, LyricsOvh, LyricsProvider};
+use crate::utils::spawn_guarded;
 
 #[macro_use]
 extern crate async_trait;
@@ -119,12 +127,19 @@ async fn async_main() -> BotResult<()> {
     let servers = RwLock::new(HashSet::new());
     let songbird = Songbird::twilight(cluster.clone(), user_id);
     let cache = InMemoryCache::builder()
-        .resource_types(ResourceType::CHANNEL | ResourceType::GUILD | ResourceType::VOICE_STATE)
+        .resource_types(
+            ResourceType::CHANNEL
+                | ResourceType::GUILD
+                | ResourceType::ROLE
+                | ResourceType::VOICE_STATE,
+        )
         .build();
 
     let database_url =
         env::var("DATABASE_URL").expect("Missing environment variable (DATABASE_URL).");
     let database = Database::new(&database_url).await?;
+    let buckets = Buckets::new(database.get_bucket_overrides().await?);
+    let music_settings = database.get_music_settings().await?.into_iter().collect();
 
     let client_id = env::var("OSU_CLIENT_ID")
         .expect("Missing environment variable (OSU_CLIENT_ID).")
@@ -160,20 +175,46 @@ async fn async_main() -> BotResult<()> {
 
     let standby = Standby::new();
 
+    let error_channel = env::var("ERROR_CHANNEL_ID")
+        .ok()
+        .and_then(|id| id.parse().ok())
+        .map(ChannelId);
+
     let stats = BotStats::new(osu.metrics());
 
     let client = Client::new();
 
+    let library = env::var("MUSIC_LIBRARY_DIR").ok().map(MusicLibrary::new);
+
+    // Lyrics can be served from local files instead of the web
+    let lyrics: Box<dyn LyricsProvider> = match env::var("LYRICS_DIR") {
+        Ok(dir) => Box::new(LyricsDirectory::new(dir)),
+        Err(_) => Box::new(LyricsOvh::new(client.clone())),
+    };
+
     let ctx = Context {
+        audio_filters: DashMap::new(),
+        buckets,
         cache,
         client,
         cluster,
         database,
+        error_channel,
         http,
         // hub,
+        idle_trackers: DashMap::new(),
         irc,
+        library,
+        loop_modes: DashMap::new(),
+        loudness: DashMap::new(),
+        lyrics,
+        music_settings,
+        now_playing: DashMap::new(),
         osu,
+        paginations: DashMap::new(),
+        played_tracks: DashMap::new(),
         servers,
+        skip_votes: DashMap::new(),
         songbird,
         standby,
         stats,
@@ -181,6 +222,8 @@ async fn async_main() -> BotResult<()> {
 
     let ctx = Arc::new(ctx);
 
+    spawn_guarded(&ctx, index_library(Arc::clone(&ctx)));
+
     tokio::select! {
         _ = event_loop(Arc::clone(&ctx), events) => {}
         _ = wait_for_ctrl_c() => {}
@@ -189,9 +232,15 @@ async fn async_main() -> BotResult<()> {
     info!("Shutting down cluster...");
     ctx.cluster.down();
 
-    info!("Clearing song queue...");
-    for server in ctx.servers.read().iter() {
-        if let Some(call) = ctx.songbird.get(server.0) {
+    info!("Saving and clearing song queues...");
+    let servers: Vec<_> = ctx.servers.read().iter().copied().collect();
+
+    for guild_id in servers {
+        if let Err(why) = commands::save_queue(&ctx, guild_id).await {
+            unwind_error!(warn, why, "Failed to save queue of guild {}: {}", guild_id);
+        }
+
+        if let Some(call) = ctx.songbird.get(guild_id) {
             let call = call.lock().await;
             call.queue().stop();
         }
@@ -207,14 +256,25 @@ async fn event_loop(ctx: Arc<Context>, mut events: Events) {
         ctx.standby.process(&event);
         let ctx = Arc::clone(&ctx);
 
-        tokio::spawn(async move {
-            if let Err(why) = handle_event(ctx, event, shard_id).await {
+        spawn_guarded(&ctx, async move {
+            if let Err(why) = handle_event(Arc::clone(&ctx), event, shard_id).await {
                 unwind_error!(error, why, "Error while handling event: {}");
             }
         });
     }
 }
 
+async fn index_library(ctx: Arc<Context>) {
+    if let Some(ref library) = ctx.library {
+        info!("Indexing music library...");
+
+        match library.index(&ctx.database).await {
+            Ok(amount) => info!("Indexed {} songs of the music library", amount),
+            Err(why) => unwind_error!(error, why, "Failed to index music library: {}"),
+        }
+    }
+}
+
 async fn wait_for_ctrl_c() {
     if let Err(why) = tokio::signal::ctrl_c().await {
         unwind_error!(error, why, "Failed to listen for ctrl-c event. {:?}");
@@ -245,11 +305,11 @@ async fn handle_event(ctx: Arc<Context>, event: Event, shard_id: u64) -> BotResu
         Event::GuildDelete(e) => {
             ctx.servers.write().remove(&e.id);
         }
-        Event::InteractionCreate(e) => {
-            if let Interaction::ApplicationCommand(command) = e.0 {
-                handle_interaction(ctx, *command).await?;
-            }
-        }
+        Event::InteractionCreate(e) => match e.0 {
+            Interaction::ApplicationCommand(command) => handle_interaction(ctx, *command).await?,
+            Interaction::MessageComponent(component) => handle_component(ctx, *component).await?,
+            _ => {}
+        },
         Event::Ready(_) => {
             info!("Shard {} is ready", shard_id);
 
@@ -277,6 +337,12 @@ async fn handle_event(ctx: Arc<Context>, event: Event, shard_id: u64) -> BotResu
         Event::ShardIdentifying(_) => info!("Shard {} is identifying...", shard_id),
         Event::ShardReconnecting(_) => info!("Shard {} is reconnecting...", shard_id),
         Event::ShardResuming(_) => info!("Shard {} is resuming...", shard_id),
+        Event::VoiceStateUpdate(e) => {
+            // Someone joining or leaving might make the bot idle or active again
+            if let Some(guild_id) = e.0.guild_id {
+                commands::check_idle(ctx, guild_id).await;
+            }
+        }
         _ => {}
     }
     Ok(())
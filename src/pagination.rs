@@ -0,0 +1,302 @@
+use std::{sync::Arc, time::Duration};
+
+use tokio::time::{self, Instant};
+use twilight_model::{
+    application::{
+        component::{button::ButtonStyle, ActionRow, Button, Component},
+        interaction::{ApplicationCommand, MessageComponentInteraction},
+    },
+    channel::embed::Embed,
+    gateway::payload::MessageCreate,
+    id::{ChannelId, MessageId, UserId},
+};
+
+use crate::{
+    context::Context,
+    error::BotResult,
+    utils::{spawn_guarded, ApplicationCommandExt, MessageBuilder, MessageComponentExt},
+};
+
+/// Buttons are disabled after this long without interaction
+const IDLE_TIMEOUT: Duration = Duration::from_secs(90);
+
+/// How long to wait for the page number after pressing the jump button
+const JUMP_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Prefix of the custom ID of all pagination buttons
+pub const PAGINATION_PREFIX: &str = "page_";
+
+const FIRST: &str = "page_first";
+const PREVIOUS: &str = "page_prev";
+const JUMP: &str = "page_jump";
+const NEXT: &str = "page_next";
+const LAST: &str = "page_last";
+
+/// Content that can be displayed over multiple pages
+pub trait Pagination: Send + Sync {
+    /// Total amount of pages, at least one
+    fn pages(&self) -> usize;
+
+    /// Render the page with the given zero-based index
+    fn render(&self, page: usize) -> Embed;
+}
+
+pub struct PaginationState {
+    owner: UserId,
+    page: usize,
+    last_interaction: Instant,
+    pagination: Box<dyn Pagination>,
+}
+
+impl PaginationState {
+    fn pages(&self) -> usize {
+        self.pagination.pages()
+    }
+}
+
+/// Respond to the command with the first page and let its author browse through the others.
+pub async fn paginate(
+    ctx: Arc<Context>,
+    command: &ApplicationCommand,
+    pagination: impl Pagination + 'static,
+) -> BotResult<()> {
+    start(ctx, command, pagination, false).await
+}
+
+/// Same as [`paginate`] for commands that started thinking already
+pub async fn paginate_deferred(
+    ctx: Arc<Context>,
+    command: &ApplicationCommand,
+    pagination: impl Pagination + 'static,
+) -> BotResult<()> {
+    start(ctx, command, pagination, true).await
+}
+
+async fn start(
+    ctx: Arc<Context>,
+    command: &ApplicationCommand,
+    pagination: impl Pagination + 'static,
+    deferred: bool,
+) -> BotResult<()> {
+    let pages = pagination.pages();
+    let embed = pagination.render(0);
+
+    if pages <= 1 {
+        return respond(&ctx, command, embed.into(), deferred).await;
+    }
+
+    let components = page_components(0, pages, false);
+    let builder = MessageBuilder::from(embed).components(&components);
+    respond(&ctx, command, builder, deferred).await?;
+
+    let message = ctx
+        .http
+        .get_interaction_original(&command.token)?
+        .exec()
+        .await?
+        .model()
+        .await?;
+
+    let state = PaginationState {
+        owner: command.user_id()?,
+        page: 0,
+        last_interaction: Instant::now(),
+        pagination: Box::new(pagination),
+    };
+
+    ctx.paginations.insert(message.id, state);
+    let timeout = idle_timeout(Arc::clone(&ctx), message.channel_id, message.id);
+    spawn_guarded(&ctx, timeout);
+
+    Ok(())
+}
+
+async fn respond(
+    ctx: &Context,
+    command: &ApplicationCommand,
+    builder: MessageBuilder<'_>,
+    deferred: bool,
+) -> BotResult<()> {
+    if deferred {
+        command.update_message(ctx, builder).await
+    } else {
+        command.create_message(ctx, builder).await
+    }
+}
+
+pub async fn handle_component(
+    ctx: Arc<Context>,
+    component: MessageComponentInteraction,
+) -> BotResult<()> {
+    let user_id = component.user_id().ok();
+
+    // Don't hold the entry across any await points
+    let (embed, components) = match ctx.paginations.get_mut(&component.message.id) {
+        Some(state) if user_id != Some(state.owner) => {
+            let builder = MessageBuilder::new()
+                .error("Only the author of the command can use these buttons")
+                .ephemeral(true);
+
+            return component.create_message(&ctx, builder).await;
+        }
+        Some(state) if component.data.custom_id == JUMP => {
+            let pages = state.pages();
+            drop(state);
+
+            return jump(ctx, component, pages).await;
+        }
+        Some(mut state) => {
+            let page = match component.data.custom_id.as_str() {
+                FIRST => 0,
+                PREVIOUS => state.page.saturating_sub(1),
+                NEXT => (state.page + 1).min(state.pages() - 1),
+                LAST => state.pages() - 1,
+                _ => state.page,
+            };
+
+            state.page = page;
+            state.last_interaction = Instant::now();
+
+            (
+                state.pagination.render(page),
+                page_components(page, state.pages(), false),
+            )
+        }
+        None => {
+            let builder = MessageBuilder::new()
+                .error("These buttons have expired, use the command again")
+                .ephemeral(true);
+
+            return component.create_message(&ctx, builder).await;
+        }
+    };
+
+    let builder = MessageBuilder::from(embed).components(&components);
+
+    component.update_message(&ctx, builder).await
+}
+
+async fn jump(
+    ctx: Arc<Context>,
+    component: MessageComponentInteraction,
+    pages: usize,
+) -> BotResult<()> {
+    let content = format!("Type the page number to jump to (1-{})", pages);
+    let builder = MessageBuilder::new().embed(content).ephemeral(true);
+    component.create_message(&ctx, builder).await?;
+
+    let owner = component.user_id().ok();
+
+    let fut = ctx
+        .standby
+        .wait_for_message(component.channel_id, move |event: &MessageCreate| {
+            Some(event.author.id) == owner
+        });
+
+    let reply = match time::timeout(JUMP_TIMEOUT, fut).await {
+        Ok(Ok(reply)) => reply,
+        _ => return Ok(()),
+    };
+
+    let page = match reply.content.trim().parse::<usize>() {
+        Ok(page) if page >= 1 && page <= pages => page - 1,
+        _ => return Ok(()),
+    };
+
+    // Missing permissions are fine, the reply just stays
+    let _ = ctx
+        .http
+        .delete_message(reply.channel_id, reply.id)
+        .exec()
+        .await;
+
+    let message_id = component.message.id;
+
+    let embed = match ctx.paginations.get_mut(&message_id) {
+        Some(mut state) => {
+            state.page = page;
+            state.last_interaction = Instant::now();
+
+            state.pagination.render(page)
+        }
+        None => return Ok(()),
+    };
+
+    let components = page_components(page, pages, false);
+
+    ctx.http
+        .update_message(component.channel_id, message_id)
+        .embeds(&[embed])?
+        .components(Some(&components))?
+        .exec()
+        .await?;
+
+    Ok(())
+}
+
+/// Disable the buttons once the pagination hasn't been used for a while
+async fn idle_timeout(ctx: Arc<Context>, channel_id: ChannelId, message_id: MessageId) {
+    loop {
+        let deadline = match ctx.paginations.get(&message_id) {
+            Some(state) => state.last_interaction + IDLE_TIMEOUT,
+            None => return,
+        };
+
+        if Instant::now() >= deadline {
+            break;
+        }
+
+        time::sleep_until(deadline).await;
+    }
+
+    if let Some((_, state)) = ctx.paginations.remove(&message_id) {
+        let components = page_components(state.page, state.pages(), true);
+
+        if let Err(why) = disable_buttons(&ctx, channel_id, message_id, &components).await {
+            unwind_error!(warn, why, "Failed to disable pagination buttons: {}");
+        }
+    }
+}
+
+async fn disable_buttons(
+    ctx: &Context,
+    channel_id: ChannelId,
+    message_id: MessageId,
+    components: &[Component],
+) -> BotResult<()> {
+    ctx.http
+        .update_message(channel_id, message_id)
+        .components(Some(components))?
+        .exec()
+        .await?;
+
+    Ok(())
+}
+
+fn page_components(page: usize, pages: usize, disabled: bool) -> Vec<Component> {
+    let at_start = disabled || page == 0;
+    let at_end = disabled || page + 1 >= pages;
+
+    let buttons = vec![
+        button(FIRST, "⏮", at_start),
+        button(PREVIOUS, "◀", at_start),
+        button(JUMP, &format!("{}/{}", page + 1, pages), disabled),
+        button(NEXT, "▶", at_end),
+        button(LAST, "⏭", at_end),
+    ];
+
+    vec![Component::ActionRow(ActionRow {
+        components: buttons,
+    })]
+}
+
+fn button(custom_id: &str, label: &str, disabled: bool) -> Component {
+    Component::Button(Button {
+        custom_id: Some(custom_id.to_owned()),
+        disabled,
+        emoji: None,
+        label: Some(label.to_owned()),
+        style: ButtonStyle::Secondary,
+        url: None,
+    })
+}
@@ -51,6 +51,7 @@ pub struct BotStats {
     pub message_counts: MessageCounters,
     pub command_counts: CommandCounters,
     pub osu_metrics: OsuCounters,
+    pub panics: IntCounter,
 }
 
 macro_rules! metric_vec {
@@ -71,6 +72,8 @@ impl BotStats {
             metric_vec!(counter: "message_commands", "Executed message commands", "name");
         let slash_commands =
             metric_vec!(counter: "slash_commands", "Executed slash commands", "name");
+        let panics =
+            IntCounter::new("panics", "Panicked event handlers and background tasks").unwrap();
 
         let registry = Registry::new_custom(Some(String::from("bathbot")), None).unwrap();
         registry.register(Box::new(event_counter.clone())).unwrap();
@@ -80,6 +83,7 @@ impl BotStats {
             .unwrap();
         registry.register(Box::new(slash_commands.clone())).unwrap();
         registry.register(Box::new(osu_metrics.clone())).unwrap();
+        registry.register(Box::new(panics.clone())).unwrap();
 
         Self {
             registry,
@@ -119,6 +123,7 @@ impl BotStats {
             },
             command_counts: CommandCounters { slash_commands },
             osu_metrics: OsuCounters { rosu: osu_metrics },
+            panics,
         }
     }
 
@@ -1,5 +1,8 @@
 use std::borrow::Cow;
-use twilight_model::{application::component::Component, channel::embed::Embed};
+use twilight_model::{
+    application::component::Component,
+    channel::{embed::Embed, message::AllowedMentions},
+};
 
 use crate::utils::RED;
 
@@ -11,6 +14,8 @@ pub struct MessageBuilder<'c> {
     pub embeds: Vec<Embed>,
     pub file: Option<(&'static str, &'c [u8])>,
     pub components: Option<&'c [Component]>,
+    pub ephemeral: bool,
+    pub allowed_mentions: Option<AllowedMentions>,
 }
 
 impl<'c> MessageBuilder<'c> {
@@ -30,13 +35,15 @@ impl<'c> MessageBuilder<'c> {
         self
     }
 
+    /// Adds a red embed, combine with `ephemeral` to only show it to the author
     pub fn error(mut self, embed: impl IntoEmbed) -> Self {
         let mut embed = embed.into_embed();
         embed.color = Some(RED);
-        info!(
-            "Returned error message \"{}\"",
-            embed.description.as_ref().unwrap()
-        );
+
+        if let Some(ref description) = embed.description {
+            info!("Returned error message \"{}\"", description);
+        }
+
         self.embeds.push(embed);
 
         self
@@ -48,12 +55,24 @@ impl<'c> MessageBuilder<'c> {
         self
     }
 
-    #[allow(dead_code)]
     pub fn components(mut self, components: &'c [Component]) -> Self {
         self.components.replace(components);
 
         self
     }
+
+    /// Only show the response to the author of the interaction
+    pub fn ephemeral(mut self, ephemeral: bool) -> Self {
+        self.ephemeral = ephemeral;
+
+        self
+    }
+
+    pub fn allowed_mentions(mut self, allowed_mentions: AllowedMentions) -> Self {
+        self.allowed_mentions.replace(allowed_mentions);
+
+        self
+    }
 }
 
 impl<'c> From<Embed> for MessageBuilder<'c> {
@@ -63,6 +82,8 @@ impl<'c> From<Embed> for MessageBuilder<'c> {
             embeds: vec![embed],
             file: None,
             components: None,
+            ephemeral: false,
+            allowed_mentions: None,
         }
     }
 }
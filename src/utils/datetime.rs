@@ -22,6 +22,32 @@ impl fmt::Display for SecToMinSecFormatter {
     }
 }
 
+/// Inverse of `sec_to_minsec`, also accepts hours like `1:02:03` and plain seconds like `90` or `90s`
+pub fn parse_minsec(s: &str) -> Option<u32> {
+    let s = s.trim();
+
+    if let Some(secs) = s.strip_suffix('s') {
+        return secs.trim_end().parse().ok();
+    }
+
+    let mut secs = 0_u32;
+    let mut parts = 0;
+
+    for (i, part) in s.split(':').enumerate() {
+        let value: u32 = part.parse().ok()?;
+
+        // Only the leading part may exceed a minute or hour
+        if i > 0 && value >= 60 {
+            return None;
+        }
+
+        secs = secs.checked_mul(60)?.checked_add(value)?;
+        parts += 1;
+    }
+
+    (parts <= 3).then(|| secs)
+}
+
 // thx saki 🙂
 pub fn how_long_ago_text(date: &DateTime<Utc>) -> HowLongAgoFormatterText {
     HowLongAgoFormatterText(date)
@@ -93,3 +119,26 @@ impl fmt::Display for HowLongAgoFormatterDynamic {
         write!(f, "<t:{}:R>", self.0)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_minsec() {
+        assert_eq!(parse_minsec("1:30"), Some(90));
+        assert_eq!(parse_minsec("1:02:03"), Some(3723));
+        assert_eq!(parse_minsec("90"), Some(90));
+        assert_eq!(parse_minsec("45s"), Some(45));
+        assert_eq!(parse_minsec("1:75"), None);
+        assert_eq!(parse_minsec("1:2:3:4"), None);
+        assert_eq!(parse_minsec(""), None);
+    }
+
+    #[test]
+    fn test_minsec_roundtrip() {
+        let secs = 754;
+
+        assert_eq!(parse_minsec(&sec_to_minsec(secs).to_string()), Some(secs));
+    }
+}
@@ -9,15 +9,18 @@ use crate::{
 use twilight_model::{
     application::{
         callback::{CallbackData, InteractionResponse},
+        component::Component,
         interaction::{application_command::CommandDataOption, ApplicationCommand},
     },
-    id::UserId,
+    channel::message::MessageFlags,
+    id::{GuildId, UserId},
 };
 
 #[async_trait]
 pub trait ApplicationCommandExt {
     fn user_id(&self) -> BotResult<UserId>;
     fn username(&self) -> BotResult<&str>;
+    fn require_guild_id(&self) -> BotResult<GuildId>;
     fn yoink_options(&mut self) -> Vec<CommandDataOption>;
     async fn create_message<'l>(
         &'l self,
@@ -25,11 +28,18 @@ pub trait ApplicationCommandExt {
         builder: impl Into<MessageBuilder<'l>> + Send + 'l,
     ) -> BotResult<()>;
     async fn start_thinking(&self, ctx: &Context) -> BotResult<()>;
+    async fn defer_ephemeral(&self, ctx: &Context) -> BotResult<()>;
     async fn update_message<'l>(
         &'l self,
         ctx: &'l Context,
         builder: impl Into<MessageBuilder<'l>> + Send + 'l,
     ) -> BotResult<()>;
+    async fn followup<'l>(
+        &'l self,
+        ctx: &'l Context,
+        builder: impl Into<MessageBuilder<'l>> + Send + 'l,
+    ) -> BotResult<()>;
+    async fn delete_response(&self, ctx: &Context) -> BotResult<()>;
 }
 
 #[async_trait]
@@ -52,11 +62,14 @@ impl ApplicationCommandExt for ApplicationCommand {
             .ok_or(Error::MissingSlashAuthor)
     }
 
+    fn require_guild_id(&self) -> BotResult<GuildId> {
+        self.guild_id.ok_or(Error::MissingGuild)
+    }
+
     fn yoink_options(&mut self) -> Vec<CommandDataOption> {
         mem::take(&mut self.data.options)
     }
 
-    //TODO: ephemeral flags in builder
     async fn create_message<'l>(
         &'l self,
         ctx: &'l Context,
@@ -64,11 +77,11 @@ impl ApplicationCommandExt for ApplicationCommand {
     ) -> BotResult<()> {
         let builder = builder.into();
         let response = InteractionResponse::ChannelMessageWithSource(CallbackData {
-            allowed_mentions: None,
-            components: None,
+            allowed_mentions: builder.allowed_mentions,
+            components: builder.components.map(<[Component]>::to_vec),
             content: builder.content.map(Cow::into_owned),
             embeds: builder.embeds,
-            flags: None,
+            flags: builder.ephemeral.then(|| MessageFlags::EPHEMERAL),
             tts: None,
         });
 
@@ -98,6 +111,25 @@ impl ApplicationCommandExt for ApplicationCommand {
         Ok(())
     }
 
+    /// Acknowledge the command such that only its author sees the loading state and response
+    async fn defer_ephemeral(&self, ctx: &Context) -> BotResult<()> {
+        let response = InteractionResponse::DeferredChannelMessageWithSource(CallbackData {
+            allowed_mentions: None,
+            components: None,
+            content: None,
+            embeds: vec![],
+            flags: Some(MessageFlags::EPHEMERAL),
+            tts: None,
+        });
+
+        ctx.http
+            .interaction_callback(self.id, &self.token, &response)
+            .exec()
+            .await?;
+
+        Ok(())
+    }
+
     async fn update_message<'l>(
         &'l self,
         ctx: &'l Context,
@@ -105,10 +137,58 @@ impl ApplicationCommandExt for ApplicationCommand {
     ) -> BotResult<()> {
         let builder = builder.into();
 
-        ctx.http
+        let mut req = ctx
+            .http
             .update_interaction_original(&self.token)?
             .content(builder.content.as_deref())?
-            .embeds(Some(&builder.embeds))?
+            .embeds(Some(&builder.embeds))?;
+
+        // Leave the components untouched unless specified
+        if let Some(components) = builder.components {
+            req = req.components(Some(components))?;
+        }
+
+        req.exec().await?;
+
+        Ok(())
+    }
+
+    /// Send an additional message after the command has been responded to
+    async fn followup<'l>(
+        &'l self,
+        ctx: &'l Context,
+        builder: impl Into<MessageBuilder<'l>> + Send + 'l,
+    ) -> BotResult<()> {
+        let builder = builder.into();
+        let files: Vec<_> = builder.file.into_iter().collect();
+
+        let mut req = ctx
+            .http
+            .create_followup_message(&self.token)?
+            .embeds(&builder.embeds)
+            .ephemeral(builder.ephemeral)
+            .files(&files);
+
+        if let Some(ref content) = builder.content {
+            req = req.content(content);
+        }
+
+        if let Some(components) = builder.components {
+            req = req.components(components)?;
+        }
+
+        if let Some(allowed_mentions) = builder.allowed_mentions {
+            req = req.allowed_mentions(allowed_mentions);
+        }
+
+        req.exec().await?;
+
+        Ok(())
+    }
+
+    async fn delete_response(&self, ctx: &Context) -> BotResult<()> {
+        ctx.http
+            .delete_interaction_original(&self.token)?
             .exec()
             .await?;
 
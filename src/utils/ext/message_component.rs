@@ -0,0 +1,88 @@
+use std::borrow::Cow;
+
+use twilight_model::{
+    application::{
+        callback::{CallbackData, InteractionResponse},
+        component::Component,
+        interaction::MessageComponentInteraction,
+    },
+    channel::message::MessageFlags,
+    id::UserId,
+};
+
+use crate::{context::Context, utils::MessageBuilder, BotResult, Error};
+
+#[async_trait]
+pub trait MessageComponentExt {
+    fn user_id(&self) -> BotResult<UserId>;
+    async fn create_message<'l>(
+        &'l self,
+        ctx: &'l Context,
+        builder: impl Into<MessageBuilder<'l>> + Send + 'l,
+    ) -> BotResult<()>;
+    async fn update_message<'l>(
+        &'l self,
+        ctx: &'l Context,
+        builder: impl Into<MessageBuilder<'l>> + Send + 'l,
+    ) -> BotResult<()>;
+}
+
+#[async_trait]
+impl MessageComponentExt for MessageComponentInteraction {
+    fn user_id(&self) -> BotResult<UserId> {
+        self.member
+            .as_ref()
+            .and_then(|member| member.user.as_ref())
+            .or_else(|| self.user.as_ref())
+            .map(|user| user.id)
+            .ok_or(Error::MissingSlashAuthor)
+    }
+
+    /// Respond with a new message
+    async fn create_message<'l>(
+        &'l self,
+        ctx: &'l Context,
+        builder: impl Into<MessageBuilder<'l>> + Send + 'l,
+    ) -> BotResult<()> {
+        let builder = builder.into();
+        let response = InteractionResponse::ChannelMessageWithSource(CallbackData {
+            allowed_mentions: builder.allowed_mentions,
+            components: builder.components.map(<[Component]>::to_vec),
+            content: builder.content.map(Cow::into_owned),
+            embeds: builder.embeds,
+            flags: builder.ephemeral.then(|| MessageFlags::EPHEMERAL),
+            tts: None,
+        });
+
+        ctx.http
+            .interaction_callback(self.id, &self.token, &response)
+            .exec()
+            .await?;
+
+        Ok(())
+    }
+
+    /// Respond by updating the message that contains the component
+    async fn update_message<'l>(
+        &'l self,
+        ctx: &'l Context,
+        builder: impl Into<MessageBuilder<'l>> + Send + 'l,
+    ) -> BotResult<()> {
+        let builder = builder.into();
+        let response = InteractionResponse::UpdateMessage(CallbackData {
+            allowed_mentions: builder.allowed_mentions,
+            components: builder.components.map(<[Component]>::to_vec),
+            content: builder.content.map(Cow::into_owned),
+            embeds: builder.embeds,
+            flags: None,
+            tts: None,
+        });
+
+        ctx.http
+            .interaction_callback(self.id, &self.token, &response)
+            .exec()
+            .await?;
+
+        Ok(())
+    }
+}
@@ -65,6 +65,18 @@ pub fn get_youtube_id(msg: &str) -> Option<&str> {
         .map(|c| c.as_str())
 }
 
+/// Direct link to an mp3, ogg, wav or flac file, e.g. a discord attachment
+pub fn is_audio_file_url(msg: &str) -> bool {
+    AUDIO_FILE_URL_MATCHER.is_match(msg)
+}
+
+pub fn get_youtube_playlist_id(msg: &str) -> Option<&str> {
+    YOUTUBE_PLAYLIST_MATCHER
+        .captures(msg)
+        .and_then(|c| c.get(1))
+        .map(|c| c.as_str())
+}
+
 #[allow(dead_code)]
 pub fn is_hit_results(msg: &str) -> bool {
     HIT_RESULTS_MATCHER.is_match(msg)
@@ -120,4 +132,10 @@ lazy_static! {
     static ref SEVEN_TWO_SEVEN: Regex = Regex::new("(?P<num>7[.,]?2[.,]?7)").unwrap();
 
     static ref YOUTUBE_LINK_MATCHER: Regex = Regex::new("http(?:s?)://(?:www\\.)?youtu(?:be\\.com/watch\\?v=|\\.be/)([\\w\\-_]*)(&(amp;)?‌​[\\w\\?‌​=]*)?").unwrap();
+
+    static ref AUDIO_FILE_URL_MATCHER: Regex =
+        Regex::new(r"(?i)^https?://\S+\.(?:mp3|ogg|wav|flac)(?:\?\S*)?$").unwrap();
+
+    static ref YOUTUBE_PLAYLIST_MATCHER: Regex =
+        Regex::new(r"https?://(?:www\.|m\.|music\.)?youtube\.com/(?:playlist|watch)\?(?:\S*&)?list=([\w\-]+)").unwrap();
 }
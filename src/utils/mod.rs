@@ -1,17 +1,20 @@
 mod builders;
 mod cow;
-mod datetime;
+pub mod datetime;
 mod ext;
 pub mod matcher;
 pub mod numbers;
+mod spawn;
 mod spreadsheet;
 mod uberduck;
+pub mod ytdl;
 
 pub use builders::author::Author;
 pub use builders::embed::EmbedBuilder;
 pub use builders::footer::Footer;
 pub use builders::message::MessageBuilder;
-pub use ext::ApplicationCommandExt;
+pub use ext::{ApplicationCommandExt, MessageComponentExt};
+pub use spawn::spawn_guarded;
 pub use spreadsheet::BatchGetResponse;
 pub use uberduck::{SpeakResponse, SpeakStatusResponse};
 
@@ -35,6 +38,9 @@ pub const FIELD_VALUE_SIZE: usize = 1024;
 pub const SPREADSHEET_BASE: &str = "https://content-sheets.googleapis.com/v4/spreadsheets/";
 pub const SUIJI_SPREADSHEET_ID: &str = "1JTVmq_sDRCfjbJht8y8kKSR0n0fCt94_3jXd-C5KS60";
 
+// lyrics
+pub const LYRICS_OVH_API: &str = "https://api.lyrics.ovh/";
+
 // uberduck
 pub const UBERDUCK_BASE: &str = "https://api.uberduck.ai/";
 
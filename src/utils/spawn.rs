@@ -0,0 +1,28 @@
+use std::{any::Any, future::Future, panic::AssertUnwindSafe, sync::Arc};
+
+use futures::FutureExt;
+
+use crate::context::Context;
+
+/// Spawn a background task whose panics are logged and counted instead of vanishing
+pub fn spawn_guarded<F>(ctx: &Arc<Context>, fut: F)
+where
+    F: Future<Output = ()> + Send + 'static,
+{
+    let ctx = Arc::clone(ctx);
+
+    tokio::spawn(async move {
+        if let Err(panic) = AssertUnwindSafe(fut).catch_unwind().await {
+            ctx.stats.panics.inc();
+            error!("Panicked in background task: {}", panic_message(&*panic));
+        }
+    });
+}
+
+fn panic_message(panic: &(dyn Any + Send)) -> &str {
+    panic
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| panic.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("<unknown panic>")
+}
@@ -0,0 +1,86 @@
+use serde::Deserialize;
+use tokio::process::Command;
+
+use crate::error::{BotResult, Error};
+
+/// Program that songbird uses to stream songs
+pub const YOUTUBE_DL: &str = "youtube-dl";
+
+/// Entry of a playlist or search as listed by youtube-dl's `--flat-playlist`
+#[derive(Deserialize)]
+pub struct PlaylistEntry {
+    pub id: String,
+    pub title: Option<String>,
+    pub channel: Option<String>,
+    pub uploader: Option<String>,
+    /// Length in seconds
+    pub duration: Option<f64>,
+}
+
+impl PlaylistEntry {
+    pub fn url(&self) -> String {
+        format!("https://www.youtube.com/watch?v={}", self.id)
+    }
+
+    pub fn channel(&self) -> Option<&str> {
+        self.channel.as_deref().or_else(|| self.uploader.as_deref())
+    }
+}
+
+/// List up to `limit` entries of the playlist without fetching the songs themselves
+pub async fn playlist_entries(url: &str, limit: usize) -> BotResult<Vec<PlaylistEntry>> {
+    let limit = limit.to_string();
+
+    flat_entries(&["--playlist-end", &limit, url]).await
+}
+
+/// Top `amount` results of a youtube search
+pub async fn search(query: &str, amount: usize) -> BotResult<Vec<PlaylistEntry>> {
+    let search = format!("ytsearch{}:{}", amount, query);
+
+    flat_entries(&[&search]).await
+}
+
+async fn flat_entries(args: &[&str]) -> BotResult<Vec<PlaylistEntry>> {
+    let output = Command::new(YOUTUBE_DL)
+        .args(&["--flat-playlist", "-j"])
+        .args(args)
+        .output()
+        .await?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+
+        return Err(Error::Ytdl(stderr.trim().to_owned()));
+    }
+
+    // One JSON object per line
+    output
+        .stdout
+        .split(|&byte| byte == b'\n')
+        .filter(|line| !line.is_empty())
+        .map(|line| serde_json::from_slice(line).map_err(Error::from))
+        .collect()
+}
+
+/// URL of the audio stream that songs of the URL are played from
+pub async fn stream_url(url: &str) -> BotResult<String> {
+    let output = Command::new(YOUTUBE_DL)
+        .args(&["-g", "-f", "webm[abr>0]/bestaudio/best", "--no-playlist"])
+        .arg(url)
+        .output()
+        .await?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+
+        return Err(Error::Ytdl(stderr.trim().to_owned()));
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    match stdout.lines().next() {
+        Some(line) if !line.is_empty() => Ok(line.to_owned()),
+        _ => Err(Error::Ytdl(format!("no stream found for {}", url))),
+    }
+}